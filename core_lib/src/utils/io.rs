@@ -119,3 +119,71 @@ pub async fn load_bytes(relative_path: &std::path::Path) -> Result<Vec<u8>, Erro
         }
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+fn get_local_storage(relative_path: &std::path::Path) -> Result<web_sys::Storage, ErrorCode> {
+    match web_sys::window().and_then(|window| window.local_storage().ok().flatten()) {
+        Some(storage) => Ok(storage),
+        None => {
+            error!(
+                "Failed to get the local storage when accessing `{:?}'",
+                relative_path
+            );
+            Err(ErrorCode::Web)
+        }
+    }
+}
+
+/// Writes a file created by the game, like a save file
+/// On the web it goes to the local storage instead
+pub fn save_string(relative_path: &std::path::Path, content: &str) -> Result<(), ErrorCode> {
+    cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            let key = relative_path.to_string_lossy();
+            match get_local_storage(relative_path)?.set_item(&key, content) {
+                Ok(()) => Ok(()),
+                Err(err) => {
+                    error!("Failed to write `{}' in the local storage: {:?}", key, err);
+                    Err(ErrorCode::Web)
+                }
+            }
+        } else {
+            let absolute_path = format_path(relative_path);
+            if let Some(parent) = absolute_path.parent() {
+                if let Err(err) = std::fs::create_dir_all(parent) {
+                    error!("Failed to create the directory `{:?}`: {:?}", parent, err);
+                    return Err(ErrorCode::IO);
+                }
+            }
+            match std::fs::write(&absolute_path, content) {
+                Ok(()) => Ok(()),
+                Err(err) => {
+                    error!("Failed to write the file `{:?}`: {:?}", absolute_path, err);
+                    Err(ErrorCode::IO)
+                }
+            }
+        }
+    }
+}
+
+/// Reads a file written by `save_string`
+pub async fn load_saved_string(relative_path: &std::path::Path) -> Result<String, ErrorCode> {
+    cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            let key = relative_path.to_string_lossy();
+            match get_local_storage(relative_path)?.get_item(&key) {
+                Ok(Some(content)) => Ok(content),
+                Ok(None) => {
+                    error!("The key `{}' is not in the local storage", key);
+                    Err(ErrorCode::NotFound)
+                }
+                Err(err) => {
+                    error!("Failed to read `{}' from the local storage: {:?}", key, err);
+                    Err(ErrorCode::Web)
+                }
+            }
+        } else {
+            load_string(relative_path).await
+        }
+    }
+}
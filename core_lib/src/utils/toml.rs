@@ -7,6 +7,19 @@ use super::debug::ErrorCode;
 pub struct Toml;
 
 impl Toml {
+    fn parse_toml(path: &Path, toml_content: &str) -> Result<toml::Table, ErrorCode> {
+        match toml_content.parse::<toml::Table>() {
+            Ok(toml) => Ok(toml),
+            Err(err) => {
+                error!(
+                    "Faile to parse the toml file `{:?}' into a toml table: {:?}",
+                    &path, err
+                );
+                Err(ErrorCode::IO)
+            }
+        }
+    }
+
     pub async fn get_toml(path: &Path) -> Result<toml::Table, ErrorCode> {
         let toml_content = match super::io::load_string(path).await {
             Ok(content) => content,
@@ -16,16 +29,24 @@ impl Toml {
             }
         };
 
-        match toml_content.parse::<toml::Table>() {
-            Ok(toml) => Ok(toml),
+        Self::parse_toml(path, &toml_content)
+    }
+
+    /// Reads a toml file written by `save_toml`
+    pub async fn get_saved_toml(path: &Path) -> Result<toml::Table, ErrorCode> {
+        let toml_content = match super::io::load_saved_string(path).await {
+            Ok(content) => content,
             Err(err) => {
-                error!(
-                    "Faile to parse the toml file `{:?}' into a toml table: {:?}",
-                    &path, err
-                );
-                Err(ErrorCode::IO)
+                error!("Failed to read the saved toml file `{:?}': {:?}", path, err);
+                return Err(err);
             }
-        }
+        };
+
+        Self::parse_toml(path, &toml_content)
+    }
+
+    pub fn save_toml(path: &Path, toml: &toml::Table) -> Result<(), ErrorCode> {
+        super::io::save_string(path, &toml.to_string())
     }
 
     pub fn get_u8(toml: &toml::Table, key: &str) -> Result<u8, ErrorCode> {
@@ -42,6 +63,20 @@ impl Toml {
         }
     }
 
+    pub fn get_i8(toml: &toml::Table, key: &str) -> Result<i8, ErrorCode> {
+        let value = toml.get(key);
+        if value.is_none() {
+            error!("Failed to read the i8 {}: key not found in the toml", key);
+            return Err(ErrorCode::NotFound);
+        }
+        if let toml::Value::Integer(value) = value.unwrap() {
+            Ok(*value as i8)
+        } else {
+            error!("Failed to read the i8 {}: wrong format in the toml", key);
+            Err(ErrorCode::NotFound)
+        }
+    }
+
     pub fn get_u16(toml: &toml::Table, key: &str) -> Result<u16, ErrorCode> {
         let value = toml.get(key);
         if value.is_none() {
@@ -70,6 +105,23 @@ impl Toml {
         }
     }
 
+    pub fn get_usize(toml: &toml::Table, key: &str) -> Result<usize, ErrorCode> {
+        let value = toml.get(key);
+        if value.is_none() {
+            error!(
+                "Failed to read the usize {}: key not found in the toml",
+                key
+            );
+            return Err(ErrorCode::NotFound);
+        }
+        if let toml::Value::Integer(value) = value.unwrap() {
+            Ok(*value as usize)
+        } else {
+            error!("Failed to read the usize {}: wrong format in the toml", key);
+            Err(ErrorCode::NotFound)
+        }
+    }
+
     pub fn get_f32(toml: &toml::Table, key: &str) -> Result<f32, ErrorCode> {
         let value = toml.get(key);
         if value.is_none() {
@@ -84,6 +136,20 @@ impl Toml {
         }
     }
 
+    pub fn get_bool(toml: &toml::Table, key: &str) -> Result<bool, ErrorCode> {
+        let value = toml.get(key);
+        if value.is_none() {
+            error!("Failed to read the bool {}: key not found in the toml", key);
+            return Err(ErrorCode::NotFound);
+        }
+        if let toml::Value::Boolean(value) = value.unwrap() {
+            Ok(*value)
+        } else {
+            error!("Failed to read the bool {}: wrong format in the toml", key);
+            Err(ErrorCode::NotFound)
+        }
+    }
+
     pub fn get_string(toml: &toml::Table, key: &str) -> Result<String, ErrorCode> {
         let value = toml.get(key);
         if value.is_none() {
@@ -150,4 +216,34 @@ impl Toml {
             Err(ErrorCode::NotFound)
         }
     }
+
+    pub fn get_table_list<'a>(
+        toml: &'a toml::Table,
+        key: &'a str,
+    ) -> Result<Vec<&'a toml::Table>, ErrorCode> {
+        let value = toml.get(key);
+        if value.is_none() {
+            error!(
+                "Failed to read the table list {}: key not found in the toml",
+                key
+            );
+            return Err(ErrorCode::NotFound);
+        }
+        if let toml::Value::Array(values) = value.unwrap() {
+            let values = values
+                .iter()
+                .filter_map(|s| match s {
+                    toml::Value::Table(s) => Some(s),
+                    _ => None,
+                })
+                .collect();
+            Ok(values)
+        } else {
+            error!(
+                "Failed to read the table list {}: wrong format in the toml",
+                key
+            );
+            Err(ErrorCode::NotFound)
+        }
+    }
 }
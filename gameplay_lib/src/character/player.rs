@@ -1,6 +1,18 @@
 use std::path::PathBuf;
 
-use core_lib::scene::{animation::movement::MovementDirection, geometry::vertex::Vertex, rendering::texture::Texture};
+use core_lib::scene::{
+    animation::movement::MovementDirection, geometry::vertex::Vertex, rendering::texture::Texture,
+};
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::{error, info};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::pokemon::{
+    daycare::Daycare,
+    friendship::{FriendshipEvent, NB_STEPS_PER_FRIENDSHIP_GAIN},
+    personality::{ShinyOdds, TrainerId},
+    storage::{party::Party, pc::Pc, StoredPokemon},
+};
 
 pub struct PlayerSprite {
     pub width: f32,
@@ -11,9 +23,9 @@ pub struct PlayerSprite {
 
 #[derive(Debug)]
 pub enum PlayerState {
-    Down(u8), 
-    Up(u8), 
-    Left(u8), 
+    Down(u8),
+    Up(u8),
+    Left(u8),
     Right(u8),
 }
 
@@ -31,27 +43,39 @@ impl PlayerSprite {
             PlayerState::Down(index) => {
                 let left = *index as f32 * width;
                 PlayerSprite {
-                    width, height, left, top:0.,
+                    width,
+                    height,
+                    left,
+                    top: 0.,
                 }
-            },
+            }
             PlayerState::Left(index) => {
                 let left = *index as f32 * width;
                 PlayerSprite {
-                    width, height, left, top:64.,
+                    width,
+                    height,
+                    left,
+                    top: 64.,
                 }
-            },
+            }
             PlayerState::Right(index) => {
                 let left = *index as f32 * width;
                 PlayerSprite {
-                    width, height, left, top:128.,
+                    width,
+                    height,
+                    left,
+                    top: 128.,
                 }
-            },
+            }
             PlayerState::Up(index) => {
                 let left = *index as f32 * width;
                 PlayerSprite {
-                    width, height, left, top:192.,
+                    width,
+                    height,
+                    left,
+                    top: 192.,
                 }
-            },
+            }
         }
     }
 }
@@ -62,6 +86,15 @@ pub struct Player {
     pub texture_height: u32,
     pub nb_steps_before_switch: u8,
     pub nb_steps: u8,
+
+    pub trainer_id: TrainerId,
+    pub shiny_odds: ShinyOdds,
+    pub party: Party,
+    pub pc: Pc,
+    pub daycare: Daycare,
+    /// Number of steps walked since the last friendship gain
+    pub nb_friendship_steps: u8,
+    pub rng: StdRng,
 }
 
 impl Player {
@@ -85,12 +118,20 @@ impl Player {
 
     pub fn new() -> Self {
         let state = PlayerState::default();
+        let mut rng = StdRng::from_entropy();
         Self {
             state,
             texture_width: 256,
             texture_height: 256,
             nb_steps_before_switch: 30,
             nb_steps: 0,
+            trainer_id: TrainerId::random(&mut rng),
+            shiny_odds: ShinyOdds::default(),
+            party: Party::default(),
+            pc: Pc::default(),
+            daycare: Daycare::default(),
+            nb_friendship_steps: 0,
+            rng,
         }
     }
 
@@ -105,9 +146,12 @@ impl Player {
         let tex_coord_height = sprite.height / texture_height;
 
         let tex_top_left = glam::Vec2::new(tex_coord_left, tex_coord_top);
-        let tex_bottom_left = glam::Vec2::new(tex_coord_left, tex_coord_top+tex_coord_height);
-        let tex_top_right = glam::Vec2::new(tex_coord_left+tex_coord_width, tex_coord_top);
-        let tex_bottom_right = glam::Vec2::new(tex_coord_left+tex_coord_width, tex_coord_top+tex_coord_height);
+        let tex_bottom_left = glam::Vec2::new(tex_coord_left, tex_coord_top + tex_coord_height);
+        let tex_top_right = glam::Vec2::new(tex_coord_left + tex_coord_width, tex_coord_top);
+        let tex_bottom_right = glam::Vec2::new(
+            tex_coord_left + tex_coord_width,
+            tex_coord_top + tex_coord_height,
+        );
 
         let mut vertices = Vertex::rectangle_vertices();
         vertices[0].tex_coords = tex_top_left;
@@ -122,7 +166,7 @@ impl Player {
         Vertex::rectangle_indices()
     }
 
-    pub fn on_move(&mut self, movement: MovementDirection){
+    pub fn on_move(&mut self, movement: MovementDirection) {
         self.nb_steps += 1;
         let should_update_step = (self.nb_steps % self.nb_steps_before_switch) == 0;
 
@@ -130,43 +174,261 @@ impl Player {
             MovementDirection::Forward => {
                 if let PlayerState::Up(step) = self.state {
                     if should_update_step {
-                        self.state = PlayerState::Up((step+1)%4);
+                        self.state = PlayerState::Up((step + 1) % 4);
                     }
                 } else {
                     self.state = PlayerState::Up(0);
                 }
-            },
+            }
             MovementDirection::Backward => {
                 if let PlayerState::Down(step) = self.state {
                     if should_update_step {
-                        self.state = PlayerState::Down((step+1)%4);
+                        self.state = PlayerState::Down((step + 1) % 4);
                     }
                 } else {
                     self.state = PlayerState::Down(0);
                 }
-            },
+            }
             MovementDirection::Left => {
                 if let PlayerState::Left(step) = self.state {
                     if should_update_step {
-                        self.state = PlayerState::Left((step+1)%4);
+                        self.state = PlayerState::Left((step + 1) % 4);
                     }
                 } else {
                     self.state = PlayerState::Left(0);
                 }
-            },
+            }
             MovementDirection::Right => {
                 if let PlayerState::Right(step) = self.state {
                     if should_update_step {
-                        self.state = PlayerState::Right((step+1)%4);
+                        self.state = PlayerState::Right((step + 1) % 4);
                     }
                 } else {
                     self.state = PlayerState::Right(0);
                 }
-            },
+            }
         }
 
         if should_update_step {
             self.nb_steps = 0;
+            self.on_step();
+        }
+    }
+
+    /// Runs every time the player walks a full step
+    pub fn on_step(&mut self) {
+        if self
+            .daycare
+            .on_step(&self.trainer_id, &self.shiny_odds, &mut self.rng)
+        {
+            info!("An egg is waiting at the daycare");
+        }
+
+        self.nb_friendship_steps += 1;
+        if self.nb_friendship_steps >= NB_STEPS_PER_FRIENDSHIP_GAIN {
+            self.nb_friendship_steps = 0;
+            for pokemon in self.party.get_pokemons_mut() {
+                pokemon.on_friendship_event(FriendshipEvent::Walking);
+            }
+        }
+
+        for index in self.party.on_step() {
+            if let Some(pokemon) = self.party.get(index) {
+                info!("The egg hatched into {}", pokemon.get_display_name());
+            }
+        }
+    }
+
+    /// Puts a new pokemon or egg in the party, or in the PC when the party is full
+    pub fn receive(&mut self, member: StoredPokemon) -> Result<(), ErrorCode> {
+        if self.party.is_full() {
+            let position = self.pc.store(member, 0)?;
+            info!(
+                "The party is full, sent to the box {}",
+                position.box_index + 1
+            );
+            return Ok(());
+        }
+        self.party.add(member)?;
+        Ok(())
+    }
+
+    pub fn collect_daycare_egg(&mut self) {
+        let has_room =
+            !self.party.is_full() || self.pc.boxes.iter().any(|pc_box| !pc_box.is_full());
+        if !has_room {
+            error!("Can't collect the daycare egg, the party and the PC are full");
+            return;
+        }
+        if let Some(egg) = self.daycare.collect_egg() {
+            if let Err(err) = self.receive(egg.into()) {
+                error!("Failed to receive the daycare egg: {:?}", err);
+            }
+        }
+    }
+
+    fn get_save_path() -> PathBuf {
+        let mut save_path = PathBuf::from("");
+        save_path.push("saves");
+        save_path.push("save");
+        save_path.set_extension("toml");
+        save_path
+    }
+
+    /// Everything kept between two sessions
+    pub fn to_save_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        toml.insert("trainer".to_string(), self.trainer_id.to_toml().into());
+        toml.insert("shiny_odds".to_string(), self.shiny_odds.to_toml().into());
+        toml.insert("nb_steps".to_string(), self.nb_steps.into());
+        toml.insert(
+            "nb_friendship_steps".to_string(),
+            self.nb_friendship_steps.into(),
+        );
+        toml.insert("party".to_string(), self.party.to_toml().into());
+        toml.insert("pc".to_string(), self.pc.to_toml().into());
+        toml.insert("daycare".to_string(), self.daycare.to_toml().into());
+        toml
+    }
+
+    /// Nothing is changed if the save is invalid
+    pub fn load_save_toml(&mut self, toml: &toml::Table) -> Result<(), ErrorCode> {
+        let trainer_id = TrainerId::from_toml(Toml::get_table(toml, "trainer")?)?;
+        let shiny_odds = ShinyOdds::from_toml(Toml::get_table(toml, "shiny_odds")?)?;
+        let nb_steps = Toml::get_u8(toml, "nb_steps")?;
+        let nb_friendship_steps = Toml::get_u8(toml, "nb_friendship_steps")?;
+        if nb_friendship_steps >= NB_STEPS_PER_FRIENDSHIP_GAIN {
+            error!(
+                "The save has {} friendship steps, it must be under {}",
+                nb_friendship_steps, NB_STEPS_PER_FRIENDSHIP_GAIN
+            );
+            return Err(ErrorCode::BadValue);
         }
+        let party = Party::from_toml(Toml::get_table(toml, "party")?)?;
+        let pc = Pc::from_toml(Toml::get_table(toml, "pc")?)?;
+        let daycare = Daycare::from_toml(Toml::get_table(toml, "daycare")?)?;
+
+        self.trainer_id = trainer_id;
+        self.shiny_odds = shiny_odds;
+        self.nb_steps = nb_steps;
+        self.nb_friendship_steps = nb_friendship_steps;
+        self.party = party;
+        self.pc = pc;
+        self.daycare = daycare;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), ErrorCode> {
+        Toml::save_toml(&Self::get_save_path(), &self.to_save_toml())
+    }
+
+    pub fn load(&mut self) -> Result<(), ErrorCode> {
+        let toml = match pollster::block_on(Toml::get_saved_toml(&Self::get_save_path())) {
+            Ok(toml) => toml,
+            Err(err) => {
+                error!("Failed to block on the save file: {:?}", err);
+                return Err(err);
+            }
+        };
+        self.load_save_toml(&toml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{
+        egg::Egg, factory::PokemonFactory, status::Status, storage::pc::BoxPosition,
+    };
+
+    use crate::pokemon::friendship::NB_STEPS_PER_FRIENDSHIP_GAIN;
+
+    use super::Player;
+
+    #[test]
+    fn save_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = Player::new();
+        player.shiny_odds.has_shiny_charm = true;
+        player.nb_steps = 12;
+        player.nb_friendship_steps = 34;
+        let factory = PokemonFactory::new(player.trainer_id, player.shiny_odds);
+
+        let mut lead = factory.generate(1, 20, &mut rng).unwrap();
+        lead.nickname = "Leafy".to_string();
+        lead.current_hp -= 5;
+        lead.status = Some(Status::Sleep(2));
+        player.receive(lead.into()).unwrap();
+        let mut egg = Egg::new(factory.generate(1, 1, &mut rng).unwrap());
+        egg.nb_cycles_left = 3;
+        player.receive(egg.into()).unwrap();
+        let boxed = factory.generate(2, 30, &mut rng).unwrap();
+        let position = player.pc.store(boxed.into(), 3).unwrap();
+        player
+            .daycare
+            .deposit(factory.generate(3, 40, &mut rng).unwrap());
+        player.daycare.egg = Some(Egg::new(factory.generate(1, 1, &mut rng).unwrap()));
+        player.daycare.nb_steps = 56;
+
+        // Goes through the text to make sure the save can be written
+        let text = toml::to_string(&player.to_save_toml()).unwrap();
+        let toml: toml::Table = toml::from_str(&text).unwrap();
+        let mut loaded = Player::new();
+        loaded.load_save_toml(&toml).unwrap();
+        assert_eq!(loaded.to_save_toml(), player.to_save_toml());
+
+        assert_eq!(loaded.trainer_id, player.trainer_id);
+        assert_eq!(loaded.shiny_odds, player.shiny_odds);
+        assert_eq!((loaded.nb_steps, loaded.nb_friendship_steps), (12, 34));
+        assert_eq!(loaded.party.len(), 2);
+        let lead = loaded.party.get_lead_pokemon().unwrap();
+        assert_eq!(lead.nickname, "Leafy");
+        assert_eq!(lead.status, Some(Status::Sleep(2)));
+        assert!(loaded.party.get(1).unwrap().is_egg());
+        assert_eq!(
+            position,
+            BoxPosition {
+                box_index: 3,
+                slot: 0
+            }
+        );
+        assert!(loaded.pc.get(position).is_some());
+        assert!(loaded.daycare.pokemon_1.is_some());
+        assert!(loaded.daycare.pokemon_2.is_none());
+        assert!(loaded.daycare.egg.is_some());
+        assert_eq!(loaded.daycare.nb_steps, 56);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn invalid_friendship_steps_are_rejected() {
+        let mut player = Player::new();
+        let mut toml = player.to_save_toml();
+        toml.insert("nb_friendship_steps".to_string(), 128.into());
+        assert!(player.load_save_toml(&toml).is_err());
+        toml.insert("nb_friendship_steps".to_string(), 127.into());
+        assert!(player.load_save_toml(&toml).is_ok());
+        assert_eq!(player.nb_friendship_steps, 127);
+    }
+
+    #[test]
+    fn walking_raises_the_friendship_every_128_steps() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = Player::new();
+        let factory = PokemonFactory::new(player.trainer_id, player.shiny_odds);
+        player
+            .receive(factory.generate(1, 5, &mut rng).unwrap().into())
+            .unwrap();
+        let friendship = player.party.get_lead_pokemon().unwrap().friendship;
+
+        player.nb_friendship_steps = NB_STEPS_PER_FRIENDSHIP_GAIN - 2;
+        player.on_step();
+        assert_eq!(
+            player.party.get_lead_pokemon().unwrap().friendship,
+            friendship
+        );
+        player.on_step();
+        assert_eq!(player.nb_friendship_steps, 0);
+        assert!(player.party.get_lead_pokemon().unwrap().friendship > friendship);
+    }
+}
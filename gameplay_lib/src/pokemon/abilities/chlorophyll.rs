@@ -0,0 +1,16 @@
+use crate::pokemon::field::{Field, Weather};
+
+use super::Ability;
+
+/// Doubles the speed under harsh sunlight
+pub struct Chlorophyll;
+
+impl Ability for Chlorophyll {
+    fn get_speed_modifier(&self, field: &Field) -> f32 {
+        if field.get_weather() == Some(Weather::Sun) {
+            2.
+        } else {
+            1.
+        }
+    }
+}
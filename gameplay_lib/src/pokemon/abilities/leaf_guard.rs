@@ -0,0 +1,15 @@
+use crate::pokemon::{
+    field::{Field, Weather},
+    status::Status,
+};
+
+use super::Ability;
+
+/// Prevents every status under harsh sunlight
+pub struct LeafGuard;
+
+impl Ability for LeafGuard {
+    fn prevents_status(&self, _status: &Status, field: &Field) -> bool {
+        field.get_weather() == Some(Weather::Sun)
+    }
+}
@@ -1 +1,120 @@
+use names::Name;
+
+use super::{
+    field::{Field, Terrain, Weather},
+    status::Status,
+    types::Type,
+    Pokemon,
+};
+
+pub mod chlorophyll;
+pub mod leaf_guard;
 pub mod names;
+pub mod overgrow;
+pub mod pressure;
+
+/// Something an ability asks the battle engine to do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbilityEffect {
+    SetWeather(Weather),
+    SetTerrain(Terrain),
+    /// The status is inflicted to the other pokemon with the given probability
+    InflictStatus {
+        status: Status,
+        chance: f64,
+    },
+}
+
+/// Behavior of an ability, every hook does nothing by default
+/// The battle engine only goes through these hooks, so a new ability only needs an implementation
+/// and an entry in `get_ability`
+pub trait Ability {
+    /// Multiplies the attack or special attack used by a move of the given type
+    fn get_attack_modifier(&self, _user: &Pokemon, _move_type: &Type) -> f32 {
+        1.
+    }
+
+    /// Multiplies the speed of the pokemon under the current weather and terrain
+    fn get_speed_modifier(&self, _field: &Field) -> f32 {
+        1.
+    }
+
+    /// Runs when the pokemon enters the battle
+    fn on_switch_in(&self) -> Option<AbilityEffect> {
+        None
+    }
+
+    /// Runs when the pokemon is hit by a contact move, the effect targets the attacker
+    fn on_contact(&self) -> Option<AbilityEffect> {
+        None
+    }
+
+    /// Tells if the ability prevents the pokemon from getting the status
+    fn prevents_status(&self, _status: &Status, _field: &Field) -> bool {
+        false
+    }
+
+    /// Additional PP used by the moves targeting the pokemon
+    fn get_extra_power_points_cost(&self) -> u8 {
+        0
+    }
+}
+
+pub fn get_ability(name: Name) -> &'static dyn Ability {
+    match name {
+        Name::Overgrow => &overgrow::Overgrow,
+        Name::Chlorophyll => &chlorophyll::Chlorophyll,
+        Name::LeafGuard => &leaf_guard::LeafGuard,
+        Name::Pressure => &pressure::Pressure,
+    }
+}
+
+impl Pokemon {
+    pub fn get_ability_effects(&self) -> &'static dyn Ability {
+        get_ability(self.ability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{
+        field::{Field, Weather},
+        status::Status,
+        test_utils::make_pokemon,
+        types::Type,
+    };
+
+    use super::{get_ability, names::Name};
+
+    #[test]
+    fn overgrow_powers_up_grass_moves_at_low_hp() {
+        let overgrow = get_ability(Name::Overgrow);
+        let mut pokemon = make_pokemon(1, 50);
+        assert_eq!(overgrow.get_attack_modifier(&pokemon, &Type::Grass), 1.);
+
+        pokemon.current_hp = pokemon.stats.hp / 3;
+        assert_eq!(overgrow.get_attack_modifier(&pokemon, &Type::Grass), 1.5);
+        assert_eq!(overgrow.get_attack_modifier(&pokemon, &Type::Normal), 1.);
+    }
+
+    #[test]
+    fn chlorophyll_doubles_the_speed_in_the_sun() {
+        let chlorophyll = get_ability(Name::Chlorophyll);
+        let mut field = Field::default();
+        assert_eq!(chlorophyll.get_speed_modifier(&field), 1.);
+        field.set_weather(Weather::Sun);
+        assert_eq!(chlorophyll.get_speed_modifier(&field), 2.);
+        field.set_weather(Weather::Rain);
+        assert_eq!(chlorophyll.get_speed_modifier(&field), 1.);
+    }
+
+    #[test]
+    fn leaf_guard_prevents_statuses_in_the_sun() {
+        let leaf_guard = get_ability(Name::LeafGuard);
+        let mut field = Field::default();
+        assert!(!leaf_guard.prevents_status(&Status::Burn, &field));
+        field.set_weather(Weather::Sun);
+        assert!(leaf_guard.prevents_status(&Status::Burn, &field));
+        assert!(!get_ability(Name::Overgrow).prevents_status(&Status::Burn, &field));
+    }
+}
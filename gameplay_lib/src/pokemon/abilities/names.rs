@@ -3,10 +3,12 @@ use std::str::FromStr;
 use core_lib::utils::debug::ErrorCode;
 use log::error;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Name {
     Overgrow,
     Chlorophyll,
+    LeafGuard,
+    Pressure,
     // TODO: Add more
 }
 
@@ -17,6 +19,8 @@ impl FromStr for Name {
         match name {
             "Overgrow" => Ok(Name::Overgrow),
             "Chlorophyll" => Ok(Name::Chlorophyll),
+            "LeafGuard" => Ok(Name::LeafGuard),
+            "Pressure" => Ok(Name::Pressure),
             _ => {
                 error!("The name {} is not a valid ability name", name);
                 Err(ErrorCode::BadValue)
@@ -0,0 +1,16 @@
+use crate::pokemon::{types::Type, Pokemon};
+
+use super::Ability;
+
+/// Powers up Grass moves when the pokemon has a third of its HP or less
+pub struct Overgrow;
+
+impl Ability for Overgrow {
+    fn get_attack_modifier(&self, user: &Pokemon, move_type: &Type) -> f32 {
+        if *move_type == Type::Grass && user.current_hp as u32 * 3 <= user.stats.hp as u32 {
+            1.5
+        } else {
+            1.
+        }
+    }
+}
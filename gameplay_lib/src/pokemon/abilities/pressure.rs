@@ -0,0 +1,10 @@
+use super::Ability;
+
+/// The moves targeting the pokemon use one more PP
+pub struct Pressure;
+
+impl Ability for Pressure {
+    fn get_extra_power_points_cost(&self) -> u8 {
+        1
+    }
+}
@@ -0,0 +1,57 @@
+use std::cmp::Ordering;
+
+/// A decision taken by the player or the AI for the next turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Uses the move in the given slot
+    UseMove(usize),
+    /// Forced when the active pokemon has no PP left in any of its moves
+    Struggle,
+    /// Switches the active pokemon with the one at the given index of the party
+    Switch(usize),
+    Run,
+}
+
+impl Action {
+    /// Running happens before switching, which happens before any move
+    fn get_bracket(&self) -> u8 {
+        match self {
+            Action::Run => 2,
+            Action::Switch(_) => 1,
+            Action::UseMove(_) | Action::Struggle => 0,
+        }
+    }
+}
+
+/// What decides which action happens first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActionOrder {
+    pub bracket: u8,
+    pub priority: i8,
+    pub speed: f32,
+}
+
+impl ActionOrder {
+    pub fn new(action: &Action, priority: i8, speed: f32) -> Self {
+        Self {
+            bracket: action.get_bracket(),
+            priority,
+            speed,
+        }
+    }
+
+    /// `None` on a speed tie
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        match self
+            .bracket
+            .cmp(&other.bracket)
+            .then(self.priority.cmp(&other.priority))
+        {
+            Ordering::Equal => match self.speed.partial_cmp(&other.speed) {
+                Some(Ordering::Equal) | None => None,
+                ordering => ordering,
+            },
+            ordering => Some(ordering),
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use rand::Rng;
+
+use crate::pokemon::{
+    field::Weather,
+    moves::{
+        effects::{EffectTarget, MoveEffect},
+        entry::MoveEntry,
+        MoveType,
+    },
+    status::Status,
+    types::Type,
+};
+
+use super::{events::BattleEvent, Battle, Side};
+
+/// Fraction in percent of an amount, at least 1 when the amount isn't 0
+fn get_percent(amount: u16, percent: u8) -> u16 {
+    if amount == 0 {
+        return 0;
+    }
+    (amount as u32 * percent as u32 / 100).max(1) as u16
+}
+
+/// Stat changes on the user of a move with a charge turn happen while it charges, like the
+/// defense boost of Skull Bash
+fn is_charge_effect(entry: &MoveEntry, effect: &MoveEffect) -> bool {
+    entry.has_effect(MoveEffect::ChargeTurn)
+        && matches!(
+            effect,
+            MoveEffect::StatChange {
+                target: EffectTarget::User,
+                ..
+            }
+        )
+}
+
+impl Battle {
+    fn roll_chance(&mut self, chance: u8) -> bool {
+        chance >= 100 || self.rng.gen_range(0..100) < chance
+    }
+
+    fn get_effect_side(side: Side, target: EffectTarget) -> Side {
+        match target {
+            EffectTarget::User => side,
+            EffectTarget::Target => side.opposite(),
+        }
+    }
+
+    /// Effects on the opponent need it to still be in battle and not behind a substitute
+    fn can_affect(&self, side: Side, target_side: Side, is_blocked: bool) -> bool {
+        target_side == side
+            || (!is_blocked && !self.get_side(target_side).get_active().is_fainted())
+    }
+
+    /// Applies the effects of a move which started charging
+    pub(super) fn apply_charge_effects(
+        &mut self,
+        side: Side,
+        entry: &MoveEntry,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        for effect in entry.effects.iter() {
+            if let MoveEffect::StatChange {
+                stat,
+                amount,
+                chance,
+                ..
+            } = *effect
+            {
+                if is_charge_effect(entry, effect) && self.roll_chance(chance) {
+                    events.push(self.change_stat_stage(side, stat, amount));
+                }
+            }
+        }
+    }
+
+    /// Interprets the effects declared by a move used by the active pokemon of the given side
+    /// The main effect of a status move failing is reported, a secondary effect failing isn't
+    pub(super) fn apply_move_effects(
+        &mut self,
+        side: Side,
+        entry: &MoveEntry,
+        damage_dealt: u16,
+        is_blocked: bool,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let is_status_move = entry.category == MoveType::Status;
+        for effect in entry.effects.iter() {
+            // Already applied on the charge turn
+            if is_charge_effect(entry, effect) {
+                continue;
+            }
+            match *effect {
+                MoveEffect::InflictStatus {
+                    status,
+                    target,
+                    chance,
+                } => {
+                    let target_side = Self::get_effect_side(side, target);
+                    if !self.can_affect(side, target_side, is_blocked) {
+                        if is_status_move {
+                            events.push(BattleEvent::MoveFailed { side });
+                        }
+                        continue;
+                    }
+                    if !self.roll_chance(chance) {
+                        continue;
+                    }
+                    let status = match status {
+                        Status::Sleep(_) => Status::random_sleep(&mut self.rng),
+                        status => status,
+                    };
+                    let field = self.field;
+                    let pokemon = self.get_side_mut(target_side).get_active_mut();
+                    if pokemon.try_set_status(status, &field) {
+                        events.push(BattleEvent::StatusInflicted {
+                            side: target_side,
+                            status,
+                        });
+                    } else if is_status_move {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::Confuse { target, chance } => {
+                    let target_side = Self::get_effect_side(side, target);
+                    let can_affect = self.can_affect(side, target_side, is_blocked);
+                    if !can_affect || !self.roll_chance(chance) {
+                        continue;
+                    }
+                    let (battle_side, rng) = self.get_side_and_rng(target_side);
+                    if battle_side.volatile.try_confuse(rng) {
+                        events.push(BattleEvent::Confused { side: target_side });
+                    } else if is_status_move {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::StatChange {
+                    stat,
+                    amount,
+                    target,
+                    chance,
+                } => {
+                    let target_side = Self::get_effect_side(side, target);
+                    if self.can_affect(side, target_side, is_blocked) && self.roll_chance(chance) {
+                        events.push(self.change_stat_stage(target_side, stat, amount));
+                    }
+                }
+                MoveEffect::Drain(percent) => {
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let amount = pokemon.heal(get_percent(damage_dealt, percent));
+                    if amount > 0 {
+                        events.push(BattleEvent::Healed { side, amount });
+                    }
+                }
+                MoveEffect::Recoil(percent) => {
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let amount = pokemon.take_damage(get_percent(damage_dealt, percent));
+                    if amount > 0 {
+                        events.push(BattleEvent::HurtByRecoil { side, amount });
+                    }
+                }
+                MoveEffect::MaxHpRecoil(percent) => {
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let amount = pokemon.take_damage(get_percent(pokemon.stats.hp, percent));
+                    if amount > 0 {
+                        events.push(BattleEvent::HurtByRecoil { side, amount });
+                    }
+                }
+                MoveEffect::Heal(percent) => {
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let amount = pokemon.heal(get_percent(pokemon.stats.hp, percent));
+                    if amount > 0 {
+                        events.push(BattleEvent::Healed { side, amount });
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::ChargeTurn
+                | MoveEffect::SolarCharge
+                | MoveEffect::SemiInvulnerableTurn => (),
+                MoveEffect::Protect => {
+                    let (battle_side, rng) = self.get_side_and_rng(side);
+                    if battle_side.volatile.try_protect(rng) {
+                        events.push(BattleEvent::Protecting { side });
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::Substitute => {
+                    let battle_side = self.get_side_mut(side);
+                    let pokemon = &mut battle_side.pokemons[battle_side.active];
+                    if battle_side.volatile.try_make_substitute(pokemon) {
+                        events.push(BattleEvent::SubstituteCreated { side });
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::Seed => {
+                    let target_side = side.opposite();
+                    let target = self.get_side_mut(target_side);
+                    let is_immune = target.get_active().pokedex_entry.has_type(&Type::Grass);
+                    if is_blocked || is_immune || target.volatile.is_seeded {
+                        events.push(BattleEvent::MoveFailed { side });
+                    } else {
+                        target.volatile.is_seeded = true;
+                        events.push(BattleEvent::Seeded { side: target_side });
+                    }
+                }
+                MoveEffect::Rest => {
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let missing_hp = pokemon.stats.hp - pokemon.current_hp;
+                    match (pokemon.rest(), pokemon.status) {
+                        (true, Some(status)) => {
+                            events.push(BattleEvent::Healed {
+                                side,
+                                amount: missing_hp,
+                            });
+                            events.push(BattleEvent::StatusInflicted { side, status });
+                        }
+                        _ => events.push(BattleEvent::MoveFailed { side }),
+                    }
+                }
+                MoveEffect::WeatherHeal => {
+                    let percent = match self.field.get_weather() {
+                        None => 50,
+                        Some(Weather::Sun) => 66,
+                        Some(_) => 25,
+                    };
+                    let pokemon = self.get_side_mut(side).get_active_mut();
+                    let amount = pokemon.heal(get_percent(pokemon.stats.hp, percent));
+                    if amount > 0 {
+                        events.push(BattleEvent::Healed { side, amount });
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::SetWeather(weather) => {
+                    if self.field.set_weather(weather) {
+                        events.push(BattleEvent::WeatherStarted(weather));
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                MoveEffect::SetTerrain(terrain) => {
+                    if self.field.set_terrain(terrain) {
+                        events.push(BattleEvent::TerrainStarted(terrain));
+                    } else {
+                        events.push(BattleEvent::MoveFailed { side });
+                    }
+                }
+                // Handled when the move is used
+                MoveEffect::WeatherType | MoveEffect::TerrainPriority(_) => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{
+        battle::{
+            action::Action,
+            events::BattleEvent,
+            stages::BattleStat,
+            tests::{make_pokemon, start_battle},
+            BattleKind, Side,
+        },
+        moves::names::Name,
+        test_utils,
+    };
+
+    fn get_damage_dealt(events: &[BattleEvent], target_side: Side) -> Option<u16> {
+        events.iter().find_map(|event| match event {
+            BattleEvent::Damaged { side, amount, .. } if *side == target_side => Some(*amount),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn drain_heals_half_of_the_damage() {
+        let mut player = make_pokemon(&[Name::GigaDrain], 100);
+        player.current_hp = 1;
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+
+        let damage = get_damage_dealt(&events, Side::Opponent).unwrap();
+        let amount = (damage / 2).max(1);
+        assert!(events.contains(&BattleEvent::Healed {
+            side: Side::Player,
+            amount
+        }));
+        assert_eq!(battle.player.get_active().current_hp, 1 + amount);
+    }
+
+    #[test]
+    fn recoil_hurts_the_user() {
+        let player = make_pokemon(&[Name::DoubleEdge], 100);
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+
+        let damage = get_damage_dealt(&events, Side::Opponent).unwrap();
+        let amount = (damage as u32 * 33 / 100).max(1) as u16;
+        assert!(events.contains(&BattleEvent::HurtByRecoil {
+            side: Side::Player,
+            amount
+        }));
+        let player = battle.player.get_active();
+        assert_eq!(player.current_hp, player.stats.hp - amount);
+    }
+
+    #[test]
+    fn skull_bash_raises_the_defense_while_charging() {
+        let player = make_pokemon(&[Name::Growl, Name::SkullBash], 100);
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+
+        let events = battle
+            .play_turn(Action::UseMove(1), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::Charging { side: Side::Player }));
+        assert!(events.contains(&BattleEvent::StatStageChanged {
+            side: Side::Player,
+            stat: BattleStat::Defense,
+            amount: 1
+        }));
+        assert_eq!(get_damage_dealt(&events, Side::Opponent), None);
+
+        // The charged move is used whatever the chosen action
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(get_damage_dealt(&events, Side::Opponent).is_some());
+        assert!(!events.iter().any(|event| matches!(
+            event,
+            BattleEvent::StatStageChanged {
+                side: Side::Player,
+                stat: BattleStat::Defense,
+                ..
+            }
+        )));
+        assert_eq!(battle.player.stages.get(BattleStat::Defense), 1);
+        let skull_bash = battle.player.get_active().moveset.get(1).unwrap();
+        assert_eq!(
+            skull_bash.current_power_points,
+            skull_bash.max_power_points - 1
+        );
+    }
+
+    #[test]
+    fn semi_invulnerable_pokemon_cant_be_hit_while_charging() {
+        let mut player = make_pokemon(&[Name::SkullBash], 100);
+        // Skull Bash turned into a move like Fly, no move of the data is semi invulnerable
+        let effects = r#"effects = [{type = "ChargeTurn"}, {type = "SemiInvulnerableTurn"}]"#;
+        player.moveset.get_mut(0).unwrap().entry =
+            test_utils::make_move_entry("skull_bash", effects);
+        let opponent = make_pokemon(&[Name::Tackle], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::Charging { side: Side::Player }));
+        assert!(events.contains(&BattleEvent::MoveMissed {
+            side: Side::Opponent
+        }));
+        assert_eq!(get_damage_dealt(&events, Side::Player), None);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(get_damage_dealt(&events, Side::Opponent).is_some());
+        assert!(get_damage_dealt(&events, Side::Player).is_some());
+    }
+
+    #[test]
+    fn consecutive_protects_get_less_likely() {
+        let player = make_pokemon(&[Name::Protect, Name::Growl], 10);
+        let opponent = make_pokemon(&[Name::Growl], 100);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::Protecting { side: Side::Player }));
+        assert!(events.contains(&BattleEvent::Protected { side: Side::Player }));
+        assert_eq!(battle.player.volatile.nb_consecutive_protects, 1);
+
+        // Each success makes the next one less likely, until one fails
+        let mut nb_protects = 1;
+        loop {
+            let events = battle
+                .play_turn(Action::UseMove(0), Action::UseMove(0))
+                .unwrap();
+            if events.contains(&BattleEvent::MoveFailed { side: Side::Player }) {
+                break;
+            }
+            nb_protects += 1;
+            assert_eq!(battle.player.volatile.nb_consecutive_protects, nb_protects);
+        }
+        assert_eq!(battle.player.volatile.nb_consecutive_protects, 0);
+
+        battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        battle
+            .play_turn(Action::UseMove(1), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(battle.player.volatile.nb_consecutive_protects, 0);
+    }
+
+    #[test]
+    fn substitute_takes_the_hits_and_blocks_the_effects() {
+        let player = make_pokemon(&[Name::Substitute], 100);
+        let opponent = make_pokemon(&[Name::Tackle, Name::Charm], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let max_hp = battle.player.get_active().stats.hp;
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(1))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::SubstituteCreated { side: Side::Player }));
+        assert_eq!(battle.player.get_active().current_hp, max_hp - max_hp / 4);
+        assert_eq!(battle.player.stages.get(BattleStat::Attack), 0);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::MoveFailed { side: Side::Player }));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            BattleEvent::SubstituteHit {
+                side: Side::Player,
+                ..
+            }
+        )));
+        assert_eq!(get_damage_dealt(&events, Side::Player), None);
+        assert_eq!(battle.player.get_active().current_hp, max_hp - max_hp / 4);
+    }
+}
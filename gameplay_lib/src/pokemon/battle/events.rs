@@ -0,0 +1,130 @@
+use crate::pokemon::{
+    field::{Terrain, Weather},
+    moves,
+    status::{BeforeMoveOutcome, Status},
+};
+
+use super::{stages::BattleStat, BattleOutcome, Side};
+
+/// Something that happened during a battle, in the order the UI should animate it
+#[derive(Debug, Clone, PartialEq)]
+pub enum BattleEvent {
+    TurnStarted(u32),
+    SwitchedIn {
+        side: Side,
+        index: usize,
+    },
+    SwitchedOut {
+        side: Side,
+        index: usize,
+    },
+    /// The active pokemon was prevented from moving or recovered before moving
+    BeforeMove {
+        side: Side,
+        outcome: BeforeMoveOutcome,
+    },
+    MoveUsed {
+        side: Side,
+        name: moves::names::Name,
+    },
+    MoveMissed {
+        side: Side,
+    },
+    MoveFailed {
+        side: Side,
+    },
+    /// The move will be used on the next turn
+    Charging {
+        side: Side,
+    },
+    Protecting {
+        side: Side,
+    },
+    /// The target protected itself
+    Protected {
+        side: Side,
+    },
+    /// The target is immune to the move
+    NoEffect {
+        side: Side,
+    },
+    Damaged {
+        side: Side,
+        amount: u16,
+        effectiveness: f32,
+        is_critical: bool,
+    },
+    SubstituteCreated {
+        side: Side,
+    },
+    SubstituteHit {
+        side: Side,
+        amount: u16,
+    },
+    SubstituteBroke {
+        side: Side,
+    },
+    HurtByConfusion {
+        side: Side,
+        amount: u16,
+    },
+    HurtByRecoil {
+        side: Side,
+        amount: u16,
+    },
+    HurtByStatus {
+        side: Side,
+        status: Status,
+        amount: u16,
+    },
+    HurtByWeather {
+        side: Side,
+        weather: Weather,
+        amount: u16,
+    },
+    /// HP taken by Leech Seed
+    Drained {
+        side: Side,
+        amount: u16,
+    },
+    Healed {
+        side: Side,
+        amount: u16,
+    },
+    StatusInflicted {
+        side: Side,
+        status: Status,
+    },
+    Confused {
+        side: Side,
+    },
+    Seeded {
+        side: Side,
+    },
+    StatStageChanged {
+        side: Side,
+        stat: BattleStat,
+        amount: i8,
+    },
+    StatStageWontGoHigher {
+        side: Side,
+        stat: BattleStat,
+    },
+    StatStageWontGoLower {
+        side: Side,
+        stat: BattleStat,
+    },
+    WeatherStarted(Weather),
+    WeatherEnded(Weather),
+    TerrainStarted(Terrain),
+    TerrainEnded(Terrain),
+    Fainted {
+        side: Side,
+        index: usize,
+    },
+    /// The side must call `Battle::replace` before the next turn
+    ReplacementNeeded(Side),
+    RanAway,
+    FailedToRun,
+    Ended(BattleOutcome),
+}
@@ -0,0 +1,389 @@
+use log::error;
+use rand::Rng;
+
+use crate::pokemon::{
+    abilities::AbilityEffect,
+    damage::{get_base_damage, DamageCalculator, DamageRoll},
+    field::{Terrain, Weather},
+    moves::{
+        effects::MoveEffect,
+        entry::{MoveEntry, MoveFlag},
+        get_global_move_entry,
+        names::Name,
+        MoveType,
+    },
+    status::BeforeMoveOutcome,
+    Pokemon,
+};
+
+use super::{
+    action::Action,
+    events::BattleEvent,
+    stages::{get_accuracy_multiplier, BattleStat},
+    Battle, BattleOutcome, Side,
+};
+
+/// Power of the typeless physical hit a confused pokemon deals to itself
+const CONFUSION_POWER: u16 = 40;
+
+impl Battle {
+    pub(super) fn execute_action(
+        &mut self,
+        side: Side,
+        action: Action,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        match action {
+            Action::UseMove(slot) => self.use_move(side, Some(slot), events),
+            Action::Struggle => self.use_move(side, None, events),
+            Action::Switch(index) => self.switch_in(side, index, events),
+            Action::Run => self.run(events),
+        }
+    }
+
+    /// Sends the pokemon at the given index of the party in battle
+    pub(super) fn switch_in(&mut self, side: Side, index: usize, events: &mut Vec<BattleEvent>) {
+        let battle_side = self.get_side_mut(side);
+        if battle_side.active != index && !battle_side.get_active().is_fainted() {
+            events.push(BattleEvent::SwitchedOut {
+                side,
+                index: battle_side.active,
+            });
+        }
+        battle_side.active = index;
+        battle_side.volatile = Default::default();
+        battle_side.stages = Default::default();
+        events.push(BattleEvent::SwitchedIn { side, index });
+
+        let ability = self.get_side(side).get_active().get_ability_effects();
+        if let Some(effect) = ability.on_switch_in() {
+            self.apply_ability_effect(side, effect, events);
+        }
+    }
+
+    /// Always works against slower pokemons, otherwise the odds grow with each attempt
+    fn run(&mut self, events: &mut Vec<BattleEvent>) {
+        self.nb_run_attempts = self.nb_run_attempts.saturating_add(1);
+        let player_speed = self.player.get_active().stats.speed as u32;
+        let opponent_speed = self.opponent.get_active().stats.speed.max(1) as u32;
+        let has_escaped = player_speed >= opponent_speed || {
+            let attempts_bonus = 30 * self.nb_run_attempts as u32;
+            let odds = (player_speed * 128 / opponent_speed + attempts_bonus) % 256;
+            self.rng.gen_range(0..256) < odds
+        };
+        if has_escaped {
+            self.outcome = Some(BattleOutcome::RanAway);
+            events.push(BattleEvent::RanAway);
+            events.push(BattleEvent::Ended(BattleOutcome::RanAway));
+        } else {
+            events.push(BattleEvent::FailedToRun);
+        }
+    }
+
+    /// Applies the persistent then the volatile status before a move
+    /// Returns true if the pokemon can use its move
+    fn check_before_move(&mut self, side: Side, events: &mut Vec<BattleEvent>) -> bool {
+        let (battle_side, rng) = self.get_side_and_rng(side);
+        let outcome = battle_side.pokemons[battle_side.active].on_status_before_move(rng);
+        if outcome != BeforeMoveOutcome::CanMove {
+            events.push(BattleEvent::BeforeMove { side, outcome });
+        }
+        if !outcome.can_move() {
+            return false;
+        }
+
+        let outcome = battle_side.volatile.on_before_move(rng);
+        if outcome != BeforeMoveOutcome::CanMove {
+            events.push(BattleEvent::BeforeMove { side, outcome });
+        }
+        if outcome == BeforeMoveOutcome::HurtItselfInConfusion {
+            let stages = battle_side.stages;
+            let pokemon = battle_side.get_active_mut();
+            let attack = pokemon.stats.attack as f32 * stages.get_multiplier(BattleStat::Attack);
+            let defense = pokemon.stats.defense as f32 * stages.get_multiplier(BattleStat::Defense);
+            let (attack, defense) = (attack as u16, defense as u16);
+            let damage = get_base_damage(pokemon.level, CONFUSION_POWER, attack, defense);
+            let amount = pokemon.take_damage(damage);
+            events.push(BattleEvent::HurtByConfusion { side, amount });
+        }
+        outcome.can_move()
+    }
+
+    /// PP used by a move, the ability of its target can raise it
+    fn get_power_points_cost(&self, side: Side, entry: &MoveEntry) -> u8 {
+        let target = self.get_side(side.opposite()).get_active();
+        if !entry.target.targets_opponent() || target.is_fainted() {
+            return 1;
+        }
+        1 + target.get_ability_effects().get_extra_power_points_cost()
+    }
+
+    /// Uses the move in the given slot, or Struggle without any slot
+    fn use_move(&mut self, side: Side, slot: Option<usize>, events: &mut Vec<BattleEvent>) {
+        let volatile = &mut self.get_side_mut(side).volatile;
+        let is_releasing = volatile.charging_slot.take().is_some();
+        volatile.is_semi_invulnerable = false;
+        if !self.check_before_move(side, events) {
+            return;
+        }
+        let entry = match slot {
+            Some(slot) => match self.get_side(side).get_active().moveset.get(slot) {
+                Some(known) => known.entry,
+                None => return,
+            },
+            None => match get_global_move_entry(Name::Struggle) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    error!("Failed to get the Struggle move: {:?}", err);
+                    return;
+                }
+            },
+        };
+        // The PP were already used when the move started charging
+        if let (Some(slot), false) = (slot, is_releasing) {
+            let cost = self.get_power_points_cost(side, entry);
+            if let Some(known) = self
+                .get_side_mut(side)
+                .get_active_mut()
+                .moveset
+                .get_mut(slot)
+            {
+                known.use_power_points(cost);
+            }
+        }
+        let volatile = &mut self.get_side_mut(side).volatile;
+        volatile.on_move_used(entry.has_effect(MoveEffect::Protect));
+        events.push(BattleEvent::MoveUsed {
+            side,
+            name: entry.name,
+        });
+
+        let is_sunny = self.field.get_weather() == Some(Weather::Sun);
+        let needs_charge = entry.has_effect(MoveEffect::ChargeTurn)
+            || (entry.has_effect(MoveEffect::SolarCharge) && !is_sunny);
+        let volatile = &mut self.get_side_mut(side).volatile;
+        if !is_releasing && needs_charge {
+            volatile.charging_slot = slot;
+            volatile.is_semi_invulnerable = entry.has_effect(MoveEffect::SemiInvulnerableTurn);
+            events.push(BattleEvent::Charging { side });
+            self.apply_charge_effects(side, entry, events);
+            return;
+        }
+        if !entry.target.targets_opponent() {
+            self.apply_move_effects(side, entry, 0, false, events);
+            return;
+        }
+
+        let target_side = side.opposite();
+        let target = self.get_side(target_side);
+        if target.get_active().is_fainted() {
+            events.push(BattleEvent::MoveFailed { side });
+            return;
+        }
+        if target.volatile.is_protected && entry.has_flag(MoveFlag::Protect) {
+            events.push(BattleEvent::Protected { side: target_side });
+            return;
+        }
+        if target.volatile.is_semi_invulnerable {
+            events.push(BattleEvent::MoveMissed { side });
+            return;
+        }
+        let target_terrain = self.field.get_terrain_for(target.get_active());
+        if target_terrain == Some(Terrain::Psychic) && self.get_move_priority(side, entry) > 0 {
+            events.push(BattleEvent::MoveFailed { side });
+            return;
+        }
+        // The substitute blocks the effects of the move on the target
+        let is_blocked = target.volatile.has_substitute() && !entry.has_flag(MoveFlag::Sound);
+        if let Some(accuracy) = entry.accuracy {
+            let stage = self.get_side(side).stages.accuracy - target.stages.evasion;
+            let accuracy = accuracy as f32 * get_accuracy_multiplier(stage);
+            if self.rng.gen_range(0.0..100.0) >= accuracy {
+                events.push(BattleEvent::MoveMissed { side });
+                return;
+            }
+        }
+
+        let mut damage_dealt = 0;
+        if entry.category != MoveType::Status && entry.power.is_some() {
+            match self.hit_target(side, entry, events) {
+                Some(damage) => damage_dealt = damage,
+                None => return,
+            }
+        }
+        self.apply_move_effects(side, entry, damage_dealt, is_blocked, events);
+    }
+
+    /// Deals the damage of the move to the opposite side
+    /// Returns the damage dealt, or `None` if the target is immune
+    fn hit_target(
+        &mut self,
+        side: Side,
+        entry: &MoveEntry,
+        events: &mut Vec<BattleEvent>,
+    ) -> Option<u16> {
+        let target_side = side.opposite();
+        let (user, target) = match side {
+            Side::Player => (&self.player, &self.opponent),
+            Side::Opponent => (&self.opponent, &self.player),
+        };
+        let mut calculator = DamageCalculator::new(user.get_active(), target.get_active(), entry);
+        calculator.attacker_stages = user.stages;
+        calculator.defender_stages = target.stages;
+        calculator.field = self.field;
+        match self.field.get_weather() {
+            Some(weather) if entry.has_effect(MoveEffect::WeatherType) => {
+                calculator.move_type = Some(weather.get_type());
+                calculator.power = Some(calculator.get_power() * 2);
+            }
+            Some(Weather::Rain | Weather::Sand | Weather::Snow)
+                if entry.has_effect(MoveEffect::SolarCharge) =>
+            {
+                calculator.power = Some(calculator.get_power() / 2);
+            }
+            _ => (),
+        }
+        let DamageRoll {
+            damage,
+            effectiveness,
+            is_critical,
+        } = calculator.roll(&mut self.rng);
+        if effectiveness == 0. {
+            events.push(BattleEvent::NoEffect { side: target_side });
+            return None;
+        }
+
+        let target = self.get_side_mut(target_side);
+        if target.volatile.has_substitute() && !entry.has_flag(MoveFlag::Sound) {
+            let amount = target.volatile.hit_substitute(damage);
+            events.push(BattleEvent::SubstituteHit {
+                side: target_side,
+                amount,
+            });
+            if !target.volatile.has_substitute() {
+                events.push(BattleEvent::SubstituteBroke { side: target_side });
+            }
+            return Some(amount);
+        }
+        let amount = target.get_active_mut().take_damage(damage);
+        events.push(BattleEvent::Damaged {
+            side: target_side,
+            amount,
+            effectiveness,
+            is_critical,
+        });
+
+        if entry.has_flag(MoveFlag::Contact) && amount > 0 {
+            let ability = target.get_active().get_ability_effects();
+            if let Some(effect) = ability.on_contact() {
+                self.apply_ability_effect(target_side, effect, events);
+            }
+        }
+        Some(amount)
+    }
+
+    /// Applies an effect asked by the ability of the active pokemon of the given side
+    fn apply_ability_effect(
+        &mut self,
+        side: Side,
+        effect: AbilityEffect,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        match effect {
+            AbilityEffect::SetWeather(weather) => {
+                if self.field.set_weather(weather) {
+                    events.push(BattleEvent::WeatherStarted(weather));
+                }
+            }
+            AbilityEffect::SetTerrain(terrain) => {
+                if self.field.set_terrain(terrain) {
+                    events.push(BattleEvent::TerrainStarted(terrain));
+                }
+            }
+            AbilityEffect::InflictStatus { status, chance } => {
+                let target_side = side.opposite();
+                let field = self.field;
+                if self.rng.gen_bool(chance)
+                    && self
+                        .get_side_mut(target_side)
+                        .get_active_mut()
+                        .try_set_status(status, &field)
+                {
+                    events.push(BattleEvent::StatusInflicted {
+                        side: target_side,
+                        status,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Damage of the weather and healing of the terrain
+    fn apply_field_end_of_turn(&mut self, events: &mut Vec<BattleEvent>) {
+        let field = self.field;
+        for side in [Side::Player, Side::Opponent] {
+            let pokemon = self.get_side_mut(side).get_active_mut();
+            if pokemon.is_fainted() {
+                continue;
+            }
+            if let Some(weather) = field.get_weather().filter(|weather| weather.hurts(pokemon)) {
+                let amount = pokemon.take_damage((pokemon.stats.hp / 16).max(1));
+                events.push(BattleEvent::HurtByWeather {
+                    side,
+                    weather,
+                    amount,
+                });
+            }
+            if !pokemon.is_fainted() && field.get_terrain_for(pokemon) == Some(Terrain::Grassy) {
+                let amount = pokemon.heal((pokemon.stats.hp / 16).max(1));
+                if amount > 0 {
+                    events.push(BattleEvent::Healed { side, amount });
+                }
+            }
+        }
+    }
+
+    /// Residual damages of the field, the statuses and Leech Seed
+    pub(super) fn end_turn(&mut self, events: &mut Vec<BattleEvent>) {
+        self.apply_field_end_of_turn(events);
+        for side in [Side::Player, Side::Opponent] {
+            let battle_side = self.get_side_mut(side);
+            let pokemon = &mut battle_side.pokemons[battle_side.active];
+            if !pokemon.is_fainted() {
+                let amount = pokemon.on_status_end_of_turn();
+                if let (Some(status), true) = (pokemon.status, amount > 0) {
+                    events.push(BattleEvent::HurtByStatus {
+                        side,
+                        status,
+                        amount,
+                    });
+                }
+            }
+
+            let drained = battle_side.volatile.on_end_of_turn(pokemon);
+            if drained == 0 {
+                continue;
+            }
+            events.push(BattleEvent::Drained {
+                side,
+                amount: drained,
+            });
+            let seeder: &mut Pokemon = self.get_side_mut(side.opposite()).get_active_mut();
+            if !seeder.is_fainted() {
+                let amount = seeder.heal(drained);
+                events.push(BattleEvent::Healed {
+                    side: side.opposite(),
+                    amount,
+                });
+            }
+        }
+
+        let (ended_weather, ended_terrain) = self.field.on_end_of_turn();
+        if let Some(weather) = ended_weather {
+            events.push(BattleEvent::WeatherEnded(weather));
+        }
+        if let Some(terrain) = ended_terrain {
+            events.push(BattleEvent::TerrainEnded(terrain));
+        }
+    }
+}
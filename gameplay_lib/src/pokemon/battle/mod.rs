@@ -0,0 +1,775 @@
+use std::cmp::Ordering;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use action::{Action, ActionOrder};
+use events::BattleEvent;
+use stages::{BattleStat, StageChange, StatStages};
+
+use super::{
+    field::Field,
+    friendship::FriendshipEvent,
+    moves::{effects::MoveEffect, entry::MoveEntry},
+    status::{volatile::VolatileStatus, Status},
+    Pokemon,
+};
+
+pub mod action;
+mod effects;
+pub mod events;
+mod execution;
+pub mod stages;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Player,
+    Opponent,
+}
+
+impl Side {
+    pub fn opposite(&self) -> Self {
+        match self {
+            Side::Player => Side::Opponent,
+            Side::Opponent => Side::Player,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleKind {
+    /// The player can run away
+    Wild,
+    Trainer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleOutcome {
+    Won,
+    Lost,
+    /// Both sides ran out of pokemons during the same turn
+    Draw,
+    RanAway,
+}
+
+/// The pokemons of one trainer, only one of them fights at a time
+pub struct BattleSide {
+    pub pokemons: Vec<Pokemon>,
+    /// Index of the fighting pokemon in `pokemons`
+    pub active: usize,
+    /// Cleared when the active pokemon leaves the battle
+    pub volatile: VolatileStatus,
+    /// Cleared when the active pokemon leaves the battle
+    pub stages: StatStages,
+    /// The active pokemon fainted and must be replaced before the next turn
+    pub needs_replacement: bool,
+}
+
+impl BattleSide {
+    fn new(pokemons: Vec<Pokemon>) -> Result<Self, ErrorCode> {
+        let active = match pokemons.iter().position(|pokemon| !pokemon.is_fainted()) {
+            Some(active) => active,
+            None => {
+                error!("A side needs at least one pokemon able to fight to start a battle");
+                return Err(ErrorCode::BadValue);
+            }
+        };
+        Ok(Self {
+            pokemons,
+            active,
+            volatile: VolatileStatus::default(),
+            stages: StatStages::default(),
+            needs_replacement: false,
+        })
+    }
+
+    pub fn get_active(&self) -> &Pokemon {
+        &self.pokemons[self.active]
+    }
+
+    pub fn get_active_mut(&mut self) -> &mut Pokemon {
+        &mut self.pokemons[self.active]
+    }
+
+    pub fn has_able_pokemon(&self) -> bool {
+        self.pokemons.iter().any(|pokemon| !pokemon.is_fainted())
+    }
+
+    pub fn can_switch_to(&self, index: usize) -> bool {
+        index != self.active
+            && self
+                .pokemons
+                .get(index)
+                .is_some_and(|pokemon| !pokemon.is_fainted())
+    }
+
+    /// Slots of the moves the active pokemon still has PP for
+    pub fn get_usable_moves(&self) -> Vec<usize> {
+        let moveset = &self.get_active().moveset;
+        (0..moveset.len())
+            .filter(|slot| {
+                moveset
+                    .get(*slot)
+                    .is_some_and(|known| known.has_power_points())
+            })
+            .collect()
+    }
+
+    /// The active pokemon has no PP left in any of its moves
+    pub fn must_struggle(&self) -> bool {
+        self.get_usable_moves().is_empty()
+    }
+
+    /// Indexes of the pokemons the active one can be switched with
+    pub fn get_switch_options(&self) -> Vec<usize> {
+        (0..self.pokemons.len())
+            .filter(|index| self.can_switch_to(*index))
+            .collect()
+    }
+}
+
+/// A single battle between the player and a wild pokemon or a trainer
+/// Nothing is rendered, the UI animates the returned events
+pub struct Battle {
+    pub kind: BattleKind,
+    pub player: BattleSide,
+    pub opponent: BattleSide,
+    pub turn: u32,
+    pub field: Field,
+    pub outcome: Option<BattleOutcome>,
+    nb_run_attempts: u8,
+    /// Seeded so that a battle can be replayed from the same decisions
+    rng: StdRng,
+}
+
+impl Battle {
+    pub fn new(
+        kind: BattleKind,
+        player_party: Vec<Pokemon>,
+        opponent_party: Vec<Pokemon>,
+        seed: u64,
+    ) -> Result<Self, ErrorCode> {
+        Ok(Self {
+            kind,
+            player: BattleSide::new(player_party)?,
+            opponent: BattleSide::new(opponent_party)?,
+            turn: 0,
+            field: Field::default(),
+            outcome: None,
+            nb_run_attempts: 0,
+            rng: StdRng::seed_from_u64(seed),
+        })
+    }
+
+    /// Sends out the leading pokemons
+    pub fn start(&mut self) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        for side in [Side::Player, Side::Opponent] {
+            let index = self.get_side(side).active;
+            self.switch_in(side, index, &mut events);
+        }
+        events
+    }
+
+    pub fn get_side(&self, side: Side) -> &BattleSide {
+        match side {
+            Side::Player => &self.player,
+            Side::Opponent => &self.opponent,
+        }
+    }
+
+    pub fn get_side_mut(&mut self, side: Side) -> &mut BattleSide {
+        match side {
+            Side::Player => &mut self.player,
+            Side::Opponent => &mut self.opponent,
+        }
+    }
+
+    /// Lets a side and the random generator be borrowed at the same time
+    fn get_side_and_rng(&mut self, side: Side) -> (&mut BattleSide, &mut StdRng) {
+        match side {
+            Side::Player => (&mut self.player, &mut self.rng),
+            Side::Opponent => (&mut self.opponent, &mut self.rng),
+        }
+    }
+
+    /// Ends the battle and gives the parties back
+    pub fn into_parties(self) -> (Vec<Pokemon>, Vec<Pokemon>) {
+        (self.player.pokemons, self.opponent.pokemons)
+    }
+
+    /// A pokemon charging a move has to use it on the next turn, whatever was chosen
+    /// A pokemon without any PP left struggles instead of using a move
+    fn resolve_action(&self, side: Side, action: Action) -> Result<Action, ErrorCode> {
+        let battle_side = self.get_side(side);
+        match (battle_side.volatile.charging_slot, action) {
+            (Some(slot), _) => Ok(Action::UseMove(slot)),
+            (None, Action::UseMove(_)) if battle_side.must_struggle() => Ok(Action::Struggle),
+            (None, action) => {
+                self.validate_action(side, &action)?;
+                Ok(action)
+            }
+        }
+    }
+
+    fn validate_action(&self, side: Side, action: &Action) -> Result<(), ErrorCode> {
+        let battle_side = self.get_side(side);
+        let is_valid = match action {
+            Action::UseMove(slot) => battle_side.get_usable_moves().contains(slot),
+            Action::Struggle => battle_side.must_struggle(),
+            Action::Switch(index) => battle_side.can_switch_to(*index),
+            Action::Run => side == Side::Player && self.kind == BattleKind::Wild,
+        };
+        if !is_valid {
+            error!(
+                "The action {:?} is not allowed for the {:?} side",
+                action, side
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(())
+    }
+
+    /// Speed of the active pokemon once its stage, its ability and its status are applied
+    pub fn get_effective_speed(&self, side: Side) -> f32 {
+        let battle_side = self.get_side(side);
+        let pokemon = battle_side.get_active();
+        let ability = pokemon.get_ability_effects();
+        let mut speed = pokemon.stats.speed as f32
+            * battle_side.stages.get_multiplier(BattleStat::Speed)
+            * ability.get_speed_modifier(&self.field);
+        if pokemon.status == Some(Status::Paralysis) {
+            speed /= 2.;
+        }
+        speed
+    }
+
+    /// Priority of a move used by the active pokemon, the terrain can raise it
+    pub fn get_move_priority(&self, side: Side, entry: &MoveEntry) -> i8 {
+        let terrain = self.field.get_terrain_for(self.get_side(side).get_active());
+        let is_boosted = entry.effects.iter().any(|effect| match effect {
+            MoveEffect::TerrainPriority(boosting_terrain) => terrain == Some(*boosting_terrain),
+            _ => false,
+        });
+        entry.priority + is_boosted as i8
+    }
+
+    fn get_action_order(&self, side: Side, action: &Action) -> ActionOrder {
+        let priority = match action {
+            Action::UseMove(slot) => self
+                .get_side(side)
+                .get_active()
+                .moveset
+                .get(*slot)
+                .map_or(0, |known| self.get_move_priority(side, known.entry)),
+            _ => 0,
+        };
+        ActionOrder::new(action, priority, self.get_effective_speed(side))
+    }
+
+    /// Sorts the actions by priority then speed, speed ties are random
+    fn sort_actions(
+        &mut self,
+        player_action: Action,
+        opponent_action: Action,
+    ) -> [(Side, Action); 2] {
+        let player_order = self.get_action_order(Side::Player, &player_action);
+        let opponent_order = self.get_action_order(Side::Opponent, &opponent_action);
+        let player_first = match player_order.compare(&opponent_order) {
+            Some(ordering) => ordering == Ordering::Greater,
+            None => self.rng.gen_bool(0.5),
+        };
+        if player_first {
+            [
+                (Side::Player, player_action),
+                (Side::Opponent, opponent_action),
+            ]
+        } else {
+            [
+                (Side::Opponent, opponent_action),
+                (Side::Player, player_action),
+            ]
+        }
+    }
+
+    /// Plays a full turn from the decisions of both sides
+    pub fn play_turn(
+        &mut self,
+        player_action: Action,
+        opponent_action: Action,
+    ) -> Result<Vec<BattleEvent>, ErrorCode> {
+        if self.outcome.is_some() {
+            error!("Can't play a turn, the battle is over");
+            return Err(ErrorCode::BadValue);
+        }
+        if self.player.needs_replacement || self.opponent.needs_replacement {
+            error!("Can't play a turn before replacing the fainted pokemons");
+            return Err(ErrorCode::BadValue);
+        }
+        let player_action = self.resolve_action(Side::Player, player_action)?;
+        let opponent_action = self.resolve_action(Side::Opponent, opponent_action)?;
+
+        self.turn += 1;
+        let mut events = vec![BattleEvent::TurnStarted(self.turn)];
+        for (side, action) in self.sort_actions(player_action, opponent_action) {
+            if self.outcome.is_some() || self.get_side(side).get_active().is_fainted() {
+                continue;
+            }
+            self.execute_action(side, action, &mut events);
+            self.check_faints(&mut events);
+        }
+        if self.outcome.is_none() {
+            self.end_turn(&mut events);
+            self.check_faints(&mut events);
+        }
+        if self.outcome.is_none() {
+            for side in [Side::Player, Side::Opponent] {
+                if self.get_side(side).needs_replacement {
+                    events.push(BattleEvent::ReplacementNeeded(side));
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Raises or lowers a stat stage of the active pokemon of the given side
+    pub fn change_stat_stage(&mut self, side: Side, stat: BattleStat, amount: i8) -> BattleEvent {
+        match self.get_side_mut(side).stages.change(stat, amount) {
+            StageChange::Changed(amount) => BattleEvent::StatStageChanged { side, stat, amount },
+            StageChange::WontGoHigher => BattleEvent::StatStageWontGoHigher { side, stat },
+            StageChange::WontGoLower => BattleEvent::StatStageWontGoLower { side, stat },
+        }
+    }
+
+    /// Sends a new pokemon after the active one fainted
+    pub fn replace(&mut self, side: Side, index: usize) -> Result<Vec<BattleEvent>, ErrorCode> {
+        let battle_side = self.get_side(side);
+        if !battle_side.needs_replacement || !battle_side.can_switch_to(index) {
+            error!(
+                "The {:?} side can't send the pokemon {} in battle",
+                side, index
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        let mut events = Vec::new();
+        self.get_side_mut(side).needs_replacement = false;
+        self.switch_in(side, index, &mut events);
+        Ok(events)
+    }
+
+    /// Reports the fainted active pokemons and ends the battle when a side has none left
+    fn check_faints(&mut self, events: &mut Vec<BattleEvent>) {
+        for side in [Side::Player, Side::Opponent] {
+            let battle_side = self.get_side_mut(side);
+            if battle_side.needs_replacement || !battle_side.get_active().is_fainted() {
+                continue;
+            }
+            battle_side.needs_replacement = true;
+            battle_side
+                .get_active_mut()
+                .on_friendship_event(FriendshipEvent::Fainted);
+            events.push(BattleEvent::Fainted {
+                side,
+                index: battle_side.active,
+            });
+        }
+
+        let outcome = match (
+            self.player.has_able_pokemon(),
+            self.opponent.has_able_pokemon(),
+        ) {
+            (false, false) => BattleOutcome::Draw,
+            (false, true) => BattleOutcome::Lost,
+            (true, false) => BattleOutcome::Won,
+            (true, true) => return,
+        };
+        self.outcome = Some(outcome);
+        events.push(BattleEvent::Ended(outcome));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{
+        abilities,
+        field::{Weather, FIELD_CONDITION_DURATION},
+        moves::{moveset::Moveset, names::Name},
+        status::Status,
+        test_utils, Pokemon,
+    };
+
+    use super::{
+        action::Action,
+        events::BattleEvent,
+        stages::{BattleStat, StatStages},
+        Battle, BattleKind, BattleOutcome, Side,
+    };
+
+    /// A level 50 Bulbasaur knowing the given moves
+    pub(super) fn make_pokemon(moves: &[Name], speed: u16) -> Pokemon {
+        let mut pokemon = test_utils::make_pokemon(1, 50);
+        pokemon.moveset = Moveset::new(moves).unwrap();
+        pokemon.stats.speed = speed;
+        pokemon
+    }
+
+    pub(super) fn start_battle(
+        kind: BattleKind,
+        player: Vec<Pokemon>,
+        opponent: Vec<Pokemon>,
+    ) -> Battle {
+        let mut battle = Battle::new(kind, player, opponent, 0).unwrap();
+        battle.start();
+        battle
+    }
+
+    /// The sides which used a move, in order
+    fn get_move_users(events: &[BattleEvent]) -> Vec<Side> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                BattleEvent::MoveUsed { side, .. } => Some(*side),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn faster_pokemon_moves_first() {
+        let player = make_pokemon(&[Name::Growl], 10);
+        let opponent = make_pokemon(&[Name::Growl], 100);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Opponent, Side::Player]);
+    }
+
+    #[test]
+    fn priority_goes_before_speed() {
+        let player = make_pokemon(&[Name::Protect], 10);
+        let opponent = make_pokemon(&[Name::Tackle], 100);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Player, Side::Opponent]);
+    }
+
+    #[test]
+    fn speed_ties_are_broken_by_the_rng() {
+        let play_first_turn = |seed: u64| {
+            let player = make_pokemon(&[Name::Growl], 50);
+            let opponent = make_pokemon(&[Name::Growl], 50);
+            let mut battle =
+                Battle::new(BattleKind::Wild, vec![player], vec![opponent], seed).unwrap();
+            battle.start();
+            battle
+                .play_turn(Action::UseMove(0), Action::UseMove(0))
+                .unwrap()
+        };
+        let first_sides: Vec<Side> = (0..32)
+            .map(|seed| get_move_users(&play_first_turn(seed))[0])
+            .collect();
+        assert!(first_sides.contains(&Side::Player));
+        assert!(first_sides.contains(&Side::Opponent));
+        // The same seed always gives the same order
+        assert_eq!(play_first_turn(7), play_first_turn(7));
+    }
+
+    #[test]
+    fn chlorophyll_doubles_the_speed_in_the_sun() {
+        let mut player = make_pokemon(&[Name::Growl], 60);
+        player.ability = abilities::names::Name::Chlorophyll;
+        let opponent = make_pokemon(&[Name::Growl], 100);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        assert_eq!(battle.get_effective_speed(Side::Player), 60.);
+
+        battle.field.set_weather(Weather::Sun);
+        assert_eq!(battle.get_effective_speed(Side::Player), 120.);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Player, Side::Opponent]);
+    }
+
+    #[test]
+    fn speed_stages_change_the_turn_order() {
+        let player = make_pokemon(&[Name::Growl], 60);
+        let opponent = make_pokemon(&[Name::Growl], 100);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        assert_eq!(
+            battle.change_stat_stage(Side::Player, BattleStat::Speed, 2),
+            BattleEvent::StatStageChanged {
+                side: Side::Player,
+                stat: BattleStat::Speed,
+                amount: 2
+            }
+        );
+        assert_eq!(battle.get_effective_speed(Side::Player), 120.);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Player, Side::Opponent]);
+    }
+
+    #[test]
+    fn stages_are_lost_when_switching_out() {
+        let player = vec![
+            make_pokemon(&[Name::Growl], 10),
+            make_pokemon(&[Name::Growl], 10),
+        ];
+        let opponent = make_pokemon(&[Name::Tackle], 100);
+        let mut battle = start_battle(BattleKind::Trainer, player, vec![opponent]);
+        battle.change_stat_stage(Side::Player, BattleStat::Attack, 6);
+        assert_eq!(
+            battle.change_stat_stage(Side::Player, BattleStat::Attack, 1),
+            BattleEvent::StatStageWontGoHigher {
+                side: Side::Player,
+                stat: BattleStat::Attack
+            }
+        );
+        battle.change_stat_stage(Side::Opponent, BattleStat::Defense, -1);
+
+        battle
+            .play_turn(Action::Switch(1), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(battle.player.stages, StatStages::default());
+        assert_eq!(battle.opponent.stages.defense, -1);
+    }
+
+    #[test]
+    fn sand_hurts_both_sides_until_it_ends() {
+        let player = make_pokemon(&[Name::Growth], 100);
+        let opponent = make_pokemon(&[Name::Growth], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let max_hp = battle.player.get_active().stats.hp;
+        battle.field.set_weather(Weather::Sand);
+
+        for turn in 1..=FIELD_CONDITION_DURATION {
+            let events = battle
+                .play_turn(Action::UseMove(0), Action::UseMove(0))
+                .unwrap();
+            for side in [Side::Player, Side::Opponent] {
+                assert!(events.contains(&BattleEvent::HurtByWeather {
+                    side,
+                    weather: Weather::Sand,
+                    amount: max_hp / 16
+                }));
+            }
+            let has_ended = events.contains(&BattleEvent::WeatherEnded(Weather::Sand));
+            assert_eq!(has_ended, turn == FIELD_CONDITION_DURATION);
+        }
+        assert_eq!(battle.field.get_weather(), None);
+        let hp_lost = (max_hp / 16) * FIELD_CONDITION_DURATION as u16;
+        assert_eq!(battle.player.get_active().current_hp, max_hp - hp_lost);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, BattleEvent::HurtByWeather { .. })));
+    }
+
+    #[test]
+    fn fainted_pokemon_must_be_replaced() {
+        let mut lead = make_pokemon(&[Name::Growl], 10);
+        lead.current_hp = 1;
+        let player = vec![lead, make_pokemon(&[Name::Growl], 10)];
+        let opponent = make_pokemon(&[Name::Tackle], 100);
+        let mut battle = start_battle(BattleKind::Trainer, player, vec![opponent]);
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::Fainted {
+            side: Side::Player,
+            index: 0
+        }));
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::ReplacementNeeded(Side::Player))
+        );
+        assert_eq!(battle.outcome, None);
+        assert!(battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .is_err());
+
+        assert!(battle.replace(Side::Player, 0).is_err());
+        let events = battle.replace(Side::Player, 1).unwrap();
+        assert!(events.contains(&BattleEvent::SwitchedIn {
+            side: Side::Player,
+            index: 1
+        }));
+        assert!(battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .is_ok());
+    }
+
+    #[test]
+    fn faster_pokemon_runs_away_from_wild_battles() {
+        let player = make_pokemon(&[Name::Growl], 100);
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let events = battle.play_turn(Action::Run, Action::UseMove(0)).unwrap();
+        assert!(events.contains(&BattleEvent::RanAway));
+        assert_eq!(get_move_users(&events), []);
+        assert_eq!(battle.outcome, Some(BattleOutcome::RanAway));
+    }
+
+    #[test]
+    fn cant_run_from_trainer_battles() {
+        let player = make_pokemon(&[Name::Growl], 100);
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Trainer, vec![player], vec![opponent]);
+        assert!(battle.play_turn(Action::Run, Action::UseMove(0)).is_err());
+        assert_eq!(battle.outcome, None);
+    }
+
+    #[test]
+    fn knocking_out_the_last_opponent_wins() {
+        let player = make_pokemon(&[Name::Tackle], 100);
+        let mut opponent = make_pokemon(&[Name::Growl], 10);
+        opponent.current_hp = 1;
+        let mut battle = start_battle(BattleKind::Trainer, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(events.last(), Some(&BattleEvent::Ended(BattleOutcome::Won)));
+        assert_eq!(get_move_users(&events), [Side::Player]);
+        assert_eq!(battle.outcome, Some(BattleOutcome::Won));
+        assert!(battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .is_err());
+    }
+
+    #[test]
+    fn losing_the_last_pokemon_loses() {
+        let mut player = make_pokemon(&[Name::Growl], 10);
+        player.current_hp = 1;
+        let opponent = make_pokemon(&[Name::Tackle], 100);
+        let mut battle = start_battle(BattleKind::Trainer, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::Ended(BattleOutcome::Lost))
+        );
+        assert_eq!(battle.outcome, Some(BattleOutcome::Lost));
+    }
+
+    #[test]
+    fn both_sides_wiped_out_is_a_draw() {
+        // The burn damage knocks out both pokemons at the end of the turn
+        let mut player = make_pokemon(&[Name::Growl], 100);
+        player.current_hp = 1;
+        player.status = Some(Status::Burn);
+        let mut opponent = make_pokemon(&[Name::Growl], 10);
+        opponent.current_hp = 1;
+        opponent.status = Some(Status::Burn);
+        let mut battle = start_battle(BattleKind::Trainer, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Player, Side::Opponent]);
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::Ended(BattleOutcome::Draw))
+        );
+        assert_eq!(battle.outcome, Some(BattleOutcome::Draw));
+    }
+
+    #[test]
+    fn pressure_doubles_the_pp_cost_of_the_moves_targeting_it() {
+        let player = make_pokemon(&[Name::Tackle, Name::Growth], 100);
+        let mut opponent = make_pokemon(&[Name::Growth], 10);
+        opponent.ability = abilities::names::Name::Pressure;
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        let get_current_power_points = |battle: &Battle, slot| {
+            battle
+                .player
+                .get_active()
+                .moveset
+                .get(slot)
+                .unwrap()
+                .current_power_points
+        };
+
+        battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_current_power_points(&battle, 0), 35 - 2);
+        // Moves which don't target the opponent cost a single PP
+        battle
+            .play_turn(Action::UseMove(1), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_current_power_points(&battle, 1), 20 - 1);
+        // The opponent's own moves aren't affected
+        let opponent_move = battle.opponent.get_active().moveset.get(0).unwrap();
+        assert_eq!(opponent_move.current_power_points, 20 - 2);
+    }
+
+    #[test]
+    fn pokemon_without_pp_must_struggle() {
+        let mut player = make_pokemon(&[Name::Tackle], 100);
+        player.moveset.get_mut(0).unwrap().use_power_points(u8::MAX);
+        let opponent = make_pokemon(&[Name::Growl], 10);
+        let mut battle = start_battle(BattleKind::Wild, vec![player], vec![opponent]);
+        assert!(battle.player.must_struggle());
+
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert!(events.contains(&BattleEvent::MoveUsed {
+            side: Side::Player,
+            name: Name::Struggle
+        }));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            BattleEvent::HurtByRecoil {
+                side: Side::Player,
+                ..
+            }
+        )));
+        assert_eq!(
+            battle
+                .player
+                .get_active()
+                .moveset
+                .get(0)
+                .unwrap()
+                .current_power_points,
+            0
+        );
+
+        // Struggling is only allowed without any PP left
+        assert!(battle
+            .play_turn(Action::Struggle, Action::UseMove(0))
+            .is_ok());
+        assert!(battle
+            .play_turn(Action::UseMove(0), Action::Struggle)
+            .is_err());
+    }
+
+    #[test]
+    fn struggle_recoil_knocking_out_both_sides_is_a_draw() {
+        let mut player = make_pokemon(&[Name::Tackle], 100);
+        player.current_hp = 1;
+        player.moveset.get_mut(0).unwrap().use_power_points(u8::MAX);
+        let mut opponent = make_pokemon(&[Name::Growl], 10);
+        opponent.current_hp = 1;
+        let mut battle = start_battle(BattleKind::Trainer, vec![player], vec![opponent]);
+        let events = battle
+            .play_turn(Action::UseMove(0), Action::UseMove(0))
+            .unwrap();
+        assert_eq!(get_move_users(&events), [Side::Player]);
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::Ended(BattleOutcome::Draw))
+        );
+        assert_eq!(battle.outcome, Some(BattleOutcome::Draw));
+    }
+}
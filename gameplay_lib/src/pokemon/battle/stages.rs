@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+pub const MIN_STAGE: i8 = -6;
+pub const MAX_STAGE: i8 = 6;
+
+/// Stats that can be raised or lowered during a battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BattleStat {
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+    Accuracy,
+    Evasion,
+}
+
+impl FromStr for BattleStat {
+    type Err = ErrorCode;
+
+    fn from_str(stat: &str) -> Result<Self, ErrorCode> {
+        match stat {
+            "Attack" => Ok(BattleStat::Attack),
+            "Defense" => Ok(BattleStat::Defense),
+            "SpecialAttack" => Ok(BattleStat::SpecialAttack),
+            "SpecialDefense" => Ok(BattleStat::SpecialDefense),
+            "Speed" => Ok(BattleStat::Speed),
+            "Accuracy" => Ok(BattleStat::Accuracy),
+            "Evasion" => Ok(BattleStat::Evasion),
+            _ => {
+                error!("The stat {} is not a valid battle stat", stat);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// Multiplier of attack, defense, special attack, special defense and speed
+pub fn get_stat_multiplier(stage: i8) -> f32 {
+    let stage = stage.clamp(MIN_STAGE, MAX_STAGE) as f32;
+    if stage >= 0. {
+        (2. + stage) / 2.
+    } else {
+        2. / (2. - stage)
+    }
+}
+
+/// Multiplier of the accuracy of a move, from the accuracy stage minus the evasion stage
+pub fn get_accuracy_multiplier(stage: i8) -> f32 {
+    let stage = stage.clamp(MIN_STAGE, MAX_STAGE) as f32;
+    if stage >= 0. {
+        (3. + stage) / 3.
+    } else {
+        3. / (3. - stage)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageChange {
+    /// The stage moved by the given amount, which can be less than asked
+    Changed(i8),
+    WontGoHigher,
+    WontGoLower,
+}
+
+/// Stages of a pokemon in battle, they are lost when it switches out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatStages {
+    pub attack: i8,
+    pub defense: i8,
+    pub special_attack: i8,
+    pub special_defense: i8,
+    pub speed: i8,
+    pub accuracy: i8,
+    pub evasion: i8,
+}
+
+impl StatStages {
+    pub fn get(&self, stat: BattleStat) -> i8 {
+        match stat {
+            BattleStat::Attack => self.attack,
+            BattleStat::Defense => self.defense,
+            BattleStat::SpecialAttack => self.special_attack,
+            BattleStat::SpecialDefense => self.special_defense,
+            BattleStat::Speed => self.speed,
+            BattleStat::Accuracy => self.accuracy,
+            BattleStat::Evasion => self.evasion,
+        }
+    }
+
+    fn get_mut(&mut self, stat: BattleStat) -> &mut i8 {
+        match stat {
+            BattleStat::Attack => &mut self.attack,
+            BattleStat::Defense => &mut self.defense,
+            BattleStat::SpecialAttack => &mut self.special_attack,
+            BattleStat::SpecialDefense => &mut self.special_defense,
+            BattleStat::Speed => &mut self.speed,
+            BattleStat::Accuracy => &mut self.accuracy,
+            BattleStat::Evasion => &mut self.evasion,
+        }
+    }
+
+    /// Raises or lowers the stage while keeping it between `MIN_STAGE` and `MAX_STAGE`
+    pub fn change(&mut self, stat: BattleStat, amount: i8) -> StageChange {
+        let stage = self.get_mut(stat);
+        let new_stage = stage.saturating_add(amount).clamp(MIN_STAGE, MAX_STAGE);
+        if new_stage == *stage && amount > 0 {
+            return StageChange::WontGoHigher;
+        }
+        if new_stage == *stage && amount < 0 {
+            return StageChange::WontGoLower;
+        }
+        let change = new_stage - *stage;
+        *stage = new_stage;
+        StageChange::Changed(change)
+    }
+
+    pub fn get_multiplier(&self, stat: BattleStat) -> f32 {
+        match stat {
+            BattleStat::Accuracy | BattleStat::Evasion => get_accuracy_multiplier(self.get(stat)),
+            _ => get_stat_multiplier(self.get(stat)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_accuracy_multiplier, get_stat_multiplier, BattleStat, StageChange, StatStages,
+    };
+
+    #[test]
+    fn stat_multipliers_go_from_a_quarter_to_four() {
+        assert_eq!(get_stat_multiplier(-6), 0.25);
+        assert_eq!(get_stat_multiplier(-1), 2. / 3.);
+        assert_eq!(get_stat_multiplier(0), 1.);
+        assert_eq!(get_stat_multiplier(2), 2.);
+        assert_eq!(get_stat_multiplier(6), 4.);
+        assert_eq!(get_stat_multiplier(12), 4.);
+    }
+
+    #[test]
+    fn accuracy_multipliers_go_from_a_third_to_three() {
+        assert_eq!(get_accuracy_multiplier(-6), 1. / 3.);
+        assert_eq!(get_accuracy_multiplier(-3), 0.5);
+        assert_eq!(get_accuracy_multiplier(0), 1.);
+        assert_eq!(get_accuracy_multiplier(3), 2.);
+        assert_eq!(get_accuracy_multiplier(6), 3.);
+    }
+
+    #[test]
+    fn stages_stop_at_the_limits() {
+        let mut stages = StatStages::default();
+        assert_eq!(
+            stages.change(BattleStat::Attack, 6),
+            StageChange::Changed(6)
+        );
+        assert_eq!(
+            stages.change(BattleStat::Attack, 1),
+            StageChange::WontGoHigher
+        );
+        assert_eq!(stages.get(BattleStat::Attack), 6);
+
+        assert_eq!(
+            stages.change(BattleStat::Evasion, -6),
+            StageChange::Changed(-6)
+        );
+        assert_eq!(
+            stages.change(BattleStat::Evasion, -2),
+            StageChange::WontGoLower
+        );
+        assert_eq!(stages.get(BattleStat::Evasion), -6);
+    }
+
+    #[test]
+    fn changes_past_the_limits_are_partial() {
+        let mut stages = StatStages::default();
+        stages.change(BattleStat::Speed, 5);
+        assert_eq!(stages.change(BattleStat::Speed, 2), StageChange::Changed(1));
+        assert_eq!(stages.get(BattleStat::Speed), 6);
+
+        stages.change(BattleStat::Defense, -5);
+        assert_eq!(
+            stages.change(BattleStat::Defense, -3),
+            StageChange::Changed(-1)
+        );
+        assert_eq!(stages.get(BattleStat::Defense), -6);
+        assert_eq!(
+            stages.change(BattleStat::Defense, 2),
+            StageChange::Changed(2)
+        );
+    }
+
+    #[test]
+    fn multipliers_depend_on_the_stat() {
+        let mut stages = StatStages::default();
+        stages.change(BattleStat::Attack, 3);
+        stages.change(BattleStat::Accuracy, 3);
+        assert_eq!(stages.get_multiplier(BattleStat::Attack), 2.5);
+        assert_eq!(stages.get_multiplier(BattleStat::Accuracy), 2.);
+        assert_eq!(stages.get_multiplier(BattleStat::Defense), 1.);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::pokemon::{types::Type, Pokemon};
+
+use super::CaptureContext;
+
+/// A ball used to catch pokemons
+/// Conditional balls can look at the target and the battle context
+pub trait Ball {
+    fn get_name(&self) -> &str;
+
+    /// Multiplies the catch rate of the target
+    fn get_modifier(&self, target: &Pokemon, context: &CaptureContext) -> f32;
+
+    /// Tells if the ball always catches the target
+    fn is_guaranteed(&self) -> bool {
+        false
+    }
+}
+
+pub struct PokeBall;
+
+impl Ball for PokeBall {
+    fn get_name(&self) -> &str {
+        "PokeBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, _context: &CaptureContext) -> f32 {
+        1.
+    }
+}
+
+pub struct GreatBall;
+
+impl Ball for GreatBall {
+    fn get_name(&self) -> &str {
+        "GreatBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, _context: &CaptureContext) -> f32 {
+        1.5
+    }
+}
+
+pub struct UltraBall;
+
+impl Ball for UltraBall {
+    fn get_name(&self) -> &str {
+        "UltraBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, _context: &CaptureContext) -> f32 {
+        2.
+    }
+}
+
+pub struct MasterBall;
+
+impl Ball for MasterBall {
+    fn get_name(&self) -> &str {
+        "MasterBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, _context: &CaptureContext) -> f32 {
+        255.
+    }
+
+    fn is_guaranteed(&self) -> bool {
+        true
+    }
+}
+
+/// Very effective on the first turn of the battle
+pub struct QuickBall;
+
+impl Ball for QuickBall {
+    fn get_name(&self) -> &str {
+        "QuickBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, context: &CaptureContext) -> f32 {
+        if context.turn <= 1 {
+            5.
+        } else {
+            1.
+        }
+    }
+}
+
+/// Effective at night or in caves
+pub struct DuskBall;
+
+impl Ball for DuskBall {
+    fn get_name(&self) -> &str {
+        "DuskBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, context: &CaptureContext) -> f32 {
+        if context.is_night_or_cave {
+            3.
+        } else {
+            1.
+        }
+    }
+}
+
+/// Effective on Water and Bug pokemons
+pub struct NetBall;
+
+impl Ball for NetBall {
+    fn get_name(&self) -> &str {
+        "NetBall"
+    }
+
+    fn get_modifier(&self, target: &Pokemon, _context: &CaptureContext) -> f32 {
+        let entry = target.pokedex_entry;
+        if entry.has_type(&Type::Water) || entry.has_type(&Type::Bug) {
+            3.5
+        } else {
+            1.
+        }
+    }
+}
+
+/// Gets better as the battle goes on
+pub struct TimerBall;
+
+impl Ball for TimerBall {
+    fn get_name(&self) -> &str {
+        "TimerBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, context: &CaptureContext) -> f32 {
+        (1. + context.turn.saturating_sub(1) as f32 * 0.3).min(4.)
+    }
+}
+
+/// Effective on low level pokemons
+pub struct NestBall;
+
+impl Ball for NestBall {
+    fn get_name(&self) -> &str {
+        "NestBall"
+    }
+
+    fn get_modifier(&self, target: &Pokemon, _context: &CaptureContext) -> f32 {
+        if target.level < 30 {
+            (41. - target.level as f32) / 10.
+        } else {
+            1.
+        }
+    }
+}
+
+/// Effective on species already caught
+pub struct RepeatBall;
+
+impl Ball for RepeatBall {
+    fn get_name(&self) -> &str {
+        "RepeatBall"
+    }
+
+    fn get_modifier(&self, _target: &Pokemon, context: &CaptureContext) -> f32 {
+        if context.is_species_caught {
+            3.5
+        } else {
+            1.
+        }
+    }
+}
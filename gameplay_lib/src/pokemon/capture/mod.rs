@@ -0,0 +1,199 @@
+use rand::Rng;
+
+use balls::Ball;
+
+use super::{status::Status, Pokemon};
+
+pub mod balls;
+
+/// Number of shakes checks before the pokemon is caught
+pub const NB_SHAKES: u8 = 4;
+
+/// Modifier depending on the status of the target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusBonus {
+    #[default]
+    None,
+    /// Paralysis, poison or burn
+    Moderate,
+    /// Sleep or freeze
+    Strong,
+}
+
+impl StatusBonus {
+    pub fn from_status(status: Option<Status>) -> Self {
+        match status {
+            None => StatusBonus::None,
+            Some(Status::Burn | Status::Paralysis | Status::Poison | Status::BadlyPoisoned(_)) => {
+                StatusBonus::Moderate
+            }
+            Some(Status::Freeze | Status::Sleep(_)) => StatusBonus::Strong,
+        }
+    }
+
+    pub fn get_multiplier(&self) -> f32 {
+        match self {
+            StatusBonus::None => 1.,
+            StatusBonus::Moderate => 1.5,
+            StatusBonus::Strong => 2.5,
+        }
+    }
+}
+
+/// The state of the battle when throwing a ball
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaptureContext {
+    /// Starts at 1
+    pub turn: u32,
+    pub is_night_or_cave: bool,
+    pub is_species_caught: bool,
+    /// Number of species registered as caught in the pokedex
+    pub nb_species_caught: u16,
+}
+
+impl CaptureContext {
+    /// The more species are caught, the more likely a critical capture is
+    fn get_critical_multiplier(&self) -> f32 {
+        match self.nb_species_caught {
+            0..=30 => 0.,
+            31..=150 => 0.5,
+            151..=300 => 1.,
+            301..=450 => 1.5,
+            451..=600 => 2.,
+            _ => 2.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureResult {
+    /// Number of shakes to animate before breaking free or being caught
+    pub nb_shakes: u8,
+    pub is_caught: bool,
+    /// A critical capture only needs one successful shake check
+    pub is_critical: bool,
+}
+
+/// Modified catch rate, the target is caught for sure when it reaches 255
+pub fn get_modified_catch_rate(target: &Pokemon, ball: &dyn Ball, context: &CaptureContext) -> f32 {
+    let max_hp = target.stats.hp.max(1) as f32;
+    let current_hp = target.current_hp as f32;
+    let catch_rate = target.pokedex_entry.catch_rate as f32;
+
+    ((3. * max_hp - 2. * current_hp) * catch_rate * ball.get_modifier(target, context))
+        / (3. * max_hp)
+        * StatusBonus::from_status(target.status).get_multiplier()
+}
+
+/// Probability out of 65536 for a single shake check to succeed
+fn get_shake_probability(modified_catch_rate: f32) -> u32 {
+    (65536. / (255. / modified_catch_rate).powf(3. / 16.)) as u32
+}
+
+/// Throws a ball at the target
+pub fn try_capture<R: Rng>(
+    target: &Pokemon,
+    ball: &dyn Ball,
+    context: &CaptureContext,
+    rng: &mut R,
+) -> CaptureResult {
+    let modified_catch_rate = get_modified_catch_rate(target, ball, context);
+    if ball.is_guaranteed() || modified_catch_rate >= 255. {
+        return CaptureResult {
+            nb_shakes: NB_SHAKES,
+            is_caught: true,
+            is_critical: false,
+        };
+    }
+
+    let critical_chance = modified_catch_rate * context.get_critical_multiplier() / 6.;
+    let is_critical = (rng.gen_range(0..256) as f32) < critical_chance;
+    let nb_checks = if is_critical { 1 } else { NB_SHAKES };
+
+    let shake_probability = get_shake_probability(modified_catch_rate);
+    let mut nb_shakes = 0;
+    while nb_shakes < nb_checks {
+        if rng.gen_range(0..65536) >= shake_probability {
+            return CaptureResult {
+                nb_shakes,
+                is_caught: false,
+                is_critical,
+            };
+        }
+        nb_shakes += 1;
+    }
+
+    CaptureResult {
+        nb_shakes,
+        is_caught: true,
+        is_critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{status::Status, test_utils::make_pokemon};
+
+    use super::{
+        balls::{GreatBall, MasterBall, NestBall, PokeBall, TimerBall},
+        get_modified_catch_rate, get_shake_probability, try_capture, CaptureContext, StatusBonus,
+        NB_SHAKES,
+    };
+
+    #[test]
+    fn catch_rate_grows_as_the_hp_drops() {
+        let context = CaptureContext::default();
+        let mut target = make_pokemon(1, 5);
+        assert_eq!(get_modified_catch_rate(&target, &PokeBall, &context), 15.);
+        assert_eq!(get_modified_catch_rate(&target, &GreatBall, &context), 22.5);
+
+        target.current_hp = 0;
+        assert_eq!(get_modified_catch_rate(&target, &PokeBall, &context), 45.);
+        assert_eq!(
+            StatusBonus::from_status(Some(Status::Burn)),
+            StatusBonus::Moderate
+        );
+        target.status = Some(Status::Sleep(1));
+        assert_eq!(get_modified_catch_rate(&target, &PokeBall, &context), 112.5);
+    }
+
+    #[test]
+    fn shake_probability_follows_the_formula() {
+        assert_eq!(get_shake_probability(15.), 38527);
+        assert_eq!(get_shake_probability(30.), 43874);
+        assert_eq!(get_shake_probability(255.), 65536);
+    }
+
+    #[test]
+    fn conditional_balls_look_at_the_context() {
+        let target = make_pokemon(1, 5);
+        let context = CaptureContext {
+            turn: 11,
+            ..Default::default()
+        };
+        assert_eq!(get_modified_catch_rate(&target, &TimerBall, &context), 60.);
+        assert_eq!(get_modified_catch_rate(&target, &NestBall, &context), 54.);
+    }
+
+    #[test]
+    fn master_balls_always_catch() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let target = make_pokemon(3, 100);
+        let result = try_capture(&target, &MasterBall, &CaptureContext::default(), &mut rng);
+        assert!(result.is_caught);
+        assert_eq!(result.nb_shakes, NB_SHAKES);
+    }
+
+    #[test]
+    fn failed_captures_stop_shaking() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let target = make_pokemon(3, 100);
+        for _ in 0..100 {
+            let result = try_capture(&target, &PokeBall, &CaptureContext::default(), &mut rng);
+            assert_eq!(result.is_caught, result.nb_shakes == NB_SHAKES);
+            assert!(!result.is_critical);
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use rand::Rng;
+
+use super::{
+    battle::stages::{get_stat_multiplier, BattleStat, StatStages},
+    field::{Field, Terrain, Weather},
+    moves::{entry::MoveEntry, MoveType},
+    status::Status,
+    types::Type,
+    Pokemon,
+};
+
+/// Multiplier of the damage of a move sharing a type with its user
+pub const STAB_MULTIPLIER: f32 = 1.5;
+
+pub const CRITICAL_HIT_MULTIPLIER: f32 = 1.5;
+
+/// Multiplier of a move hitting more than one target
+pub const SPREAD_MULTIPLIER: f32 = 0.75;
+
+/// Multiplier of a move powered up by the terrain
+pub const TERRAIN_MULTIPLIER: f32 = 1.3;
+
+/// Multiplier of the defense of Rock pokemons in a sandstorm and Ice pokemons in the snow
+pub const WEATHER_DEFENSE_MULTIPLIER: f32 = 1.5;
+
+/// The random roll goes from 85% to 100% of the damage
+pub const MIN_ROLL: u8 = 85;
+pub const MAX_ROLL: u8 = 100;
+
+/// Chance to land a critical hit for the given critical hit stage
+pub fn get_critical_hit_chance(stage: u8) -> f64 {
+    match stage {
+        0 => 1. / 24.,
+        1 => 1. / 8.,
+        2 => 1. / 2.,
+        _ => 1.,
+    }
+}
+
+/// Damage before any modifier
+pub fn get_base_damage(level: u8, power: u16, attack: u16, defense: u16) -> u16 {
+    let level_factor = 2 * level as u32 / 5 + 2;
+    let damage = level_factor * power as u32 * attack as u32 / defense.max(1) as u32 / 50 + 2;
+    damage.min(u16::MAX as u32) as u16
+}
+
+/// How the weather changes the damage of a move of the given type
+pub fn get_weather_multiplier(weather: Option<Weather>, move_type: &Type) -> f32 {
+    match (weather, move_type) {
+        (Some(Weather::Sun), Type::Fire) | (Some(Weather::Rain), Type::Water) => 1.5,
+        (Some(Weather::Sun), Type::Water) | (Some(Weather::Rain), Type::Fire) => 0.5,
+        _ => 1.,
+    }
+}
+
+/// How the terrain changes the damage of a move of the given type
+/// The terrain only applies to the grounded attackers, or the grounded defenders for Misty Terrain
+pub fn get_terrain_multiplier(
+    field: &Field,
+    attacker: &Pokemon,
+    defender: &Pokemon,
+    move_type: &Type,
+) -> f32 {
+    if field.get_terrain_for(defender) == Some(Terrain::Misty) && *move_type == Type::Dragon {
+        return 0.5;
+    }
+    match field
+        .get_terrain_for(attacker)
+        .and_then(|terrain| terrain.get_boosted_type())
+    {
+        Some(boosted_type) if boosted_type == *move_type => TERRAIN_MULTIPLIER,
+        _ => 1.,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageRange {
+    pub min: u16,
+    pub max: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageRoll {
+    pub damage: u16,
+    pub effectiveness: f32,
+    pub is_critical: bool,
+}
+
+/// Everything needed to compute the damage of a move
+/// Usable outside of battles, for example to balance the base stats
+#[derive(Clone, Copy)]
+pub struct DamageCalculator<'a> {
+    pub attacker: &'a Pokemon,
+    pub defender: &'a Pokemon,
+    pub move_entry: &'a MoveEntry,
+    /// Overrides the power of the move, used by moves whose power changes
+    pub power: Option<u16>,
+    /// Overrides the type of the move, used by moves whose type changes
+    pub move_type: Option<Type>,
+    pub attacker_stages: StatStages,
+    pub defender_stages: StatStages,
+    pub field: Field,
+    pub critical_hit_stage: u8,
+    /// The move hits more than one target
+    pub is_spread: bool,
+}
+
+impl<'a> DamageCalculator<'a> {
+    pub fn new(attacker: &'a Pokemon, defender: &'a Pokemon, move_entry: &'a MoveEntry) -> Self {
+        Self {
+            attacker,
+            defender,
+            move_entry,
+            power: None,
+            move_type: None,
+            attacker_stages: StatStages::default(),
+            defender_stages: StatStages::default(),
+            field: Field::default(),
+            critical_hit_stage: 0,
+            is_spread: false,
+        }
+    }
+
+    pub fn get_power(&self) -> u16 {
+        self.power
+            .or(self.move_entry.power.map(u16::from))
+            .unwrap_or(0)
+    }
+
+    pub fn get_move_type(&self) -> Type {
+        self.move_type.unwrap_or(self.move_entry.element_type)
+    }
+
+    pub fn get_effectiveness(&self) -> f32 {
+        self.defender
+            .pokedex_entry
+            .get_effectiveness_from(&self.get_move_type())
+    }
+
+    /// Attack and defense stats used by the move
+    /// Critical hits ignore the lowered attack and the raised defense
+    fn get_attack_and_defense(&self, is_critical: bool) -> (u16, u16) {
+        let (attack, defense, attack_stat, defense_stat) = match self.move_entry.category {
+            MoveType::Special => (
+                self.attacker.stats.special_attack,
+                self.defender.stats.special_defense,
+                BattleStat::SpecialAttack,
+                BattleStat::SpecialDefense,
+            ),
+            _ => (
+                self.attacker.stats.attack,
+                self.defender.stats.defense,
+                BattleStat::Attack,
+                BattleStat::Defense,
+            ),
+        };
+        let mut attack_stage = self.attacker_stages.get(attack_stat);
+        let mut defense_stage = self.defender_stages.get(defense_stat);
+        if is_critical {
+            attack_stage = attack_stage.max(0);
+            defense_stage = defense_stage.min(0);
+        }
+
+        let ability = self.attacker.get_ability_effects();
+        let attack_modifier = ability.get_attack_modifier(self.attacker, &self.get_move_type());
+        let attack = attack as f32 * get_stat_multiplier(attack_stage) * attack_modifier;
+        let mut defense = defense as f32 * get_stat_multiplier(defense_stage);
+
+        let defender = self.defender.pokedex_entry;
+        let is_weather_boosted = match (self.field.get_weather(), defense_stat) {
+            (Some(Weather::Sand), BattleStat::SpecialDefense) => defender.has_type(&Type::Rock),
+            (Some(Weather::Snow), BattleStat::Defense) => defender.has_type(&Type::Ice),
+            _ => false,
+        };
+        if is_weather_boosted {
+            defense *= WEATHER_DEFENSE_MULTIPLIER;
+        }
+        (attack as u16, defense as u16)
+    }
+
+    /// Damage for the given random roll, between `MIN_ROLL` and `MAX_ROLL`
+    pub fn get_damage(&self, roll: u8, is_critical: bool) -> u16 {
+        let power = self.get_power();
+        let effectiveness = self.get_effectiveness();
+        if power == 0 || self.move_entry.category == MoveType::Status || effectiveness == 0. {
+            return 0;
+        }
+
+        let (attack, defense) = self.get_attack_and_defense(is_critical);
+        let mut damage = get_base_damage(self.attacker.level, power, attack, defense) as f32;
+        if self.is_spread {
+            damage *= SPREAD_MULTIPLIER;
+        }
+        let move_type = self.get_move_type();
+        damage *= get_weather_multiplier(self.field.get_weather(), &move_type);
+        damage *= get_terrain_multiplier(&self.field, self.attacker, self.defender, &move_type);
+        if is_critical {
+            damage *= CRITICAL_HIT_MULTIPLIER;
+        }
+        damage = (damage * roll.clamp(MIN_ROLL, MAX_ROLL) as f32 / 100.).floor();
+        if self.attacker.pokedex_entry.has_type(&move_type) {
+            damage *= STAB_MULTIPLIER;
+        }
+        damage *= effectiveness;
+        let is_physical = self.move_entry.category == MoveType::Physique;
+        if is_physical && self.attacker.status == Some(Status::Burn) {
+            damage *= 0.5;
+        }
+        (damage as u16).max(1)
+    }
+
+    /// Lowest and highest damage the move can deal
+    pub fn get_range(&self, is_critical: bool) -> DamageRange {
+        DamageRange {
+            min: self.get_damage(MIN_ROLL, is_critical),
+            max: self.get_damage(MAX_ROLL, is_critical),
+        }
+    }
+
+    /// Rolls the critical hit and the random factor
+    pub fn roll<R: Rng>(&self, rng: &mut R) -> DamageRoll {
+        let is_critical = rng.gen_bool(get_critical_hit_chance(self.critical_hit_stage));
+        DamageRoll {
+            damage: self.get_damage(rng.gen_range(MIN_ROLL..=MAX_ROLL), is_critical),
+            effectiveness: self.get_effectiveness(),
+            is_critical,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{
+        battle::stages::BattleStat,
+        field::{Terrain, Weather},
+        moves::{get_global_move_entry, names::Name},
+        status::Status,
+        test_utils,
+        types::Type,
+        Pokemon,
+    };
+
+    use super::{get_critical_hit_chance, DamageCalculator, DamageRange};
+
+    /// A level 50 Bulbasaur with 100 in every stat
+    fn make_pokemon() -> Pokemon {
+        let mut pokemon = test_utils::make_pokemon(1, 50);
+        pokemon.stats.attack = 100;
+        pokemon.stats.defense = 100;
+        pokemon.stats.special_attack = 100;
+        pokemon.stats.special_defense = 100;
+        pokemon
+    }
+
+    fn make_defender(types: &[Type]) -> Pokemon {
+        let mut defender = make_pokemon();
+        defender.pokedex_entry = test_utils::make_pokedex_entry(types);
+        defender
+    }
+
+    #[test]
+    fn matches_the_mainline_damage_range() {
+        // Level 50, 40 power, 100 attack against 100 defense, without any modifier
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        assert_eq!(
+            calculator.get_range(false),
+            DamageRange { min: 16, max: 19 }
+        );
+    }
+
+    #[test]
+    fn same_type_moves_get_the_bonus() {
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let vine_whip = get_global_move_entry(Name::VineWhip).unwrap();
+        let calculator = DamageCalculator::new(&attacker, &defender, vine_whip);
+        assert_eq!(calculator.get_damage(100, false), 31);
+
+        let fire_whip = test_utils::make_move_entry("vine_whip", r#"type = "Fire""#);
+        let calculator = DamageCalculator::new(&attacker, &defender, fire_whip);
+        assert_eq!(calculator.get_damage(100, false), 21);
+    }
+
+    #[test]
+    fn type_matchups_multiply_the_damage() {
+        let attacker = make_pokemon();
+        let fire_tackle = test_utils::make_move_entry("tackle", r#"type = "Fire""#);
+        let defender = make_defender(&[Type::Grass, Type::Bug]);
+        let calculator = DamageCalculator::new(&attacker, &defender, fire_tackle);
+        assert_eq!(calculator.get_effectiveness(), 4.);
+        assert_eq!(calculator.get_damage(100, false), 76);
+
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let defender = make_defender(&[Type::Ghost]);
+        let calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        assert_eq!(calculator.get_effectiveness(), 0.);
+        assert_eq!(calculator.get_range(false), DamageRange { min: 0, max: 0 });
+    }
+
+    #[test]
+    fn burn_halves_physical_damage_only() {
+        let (mut attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        attacker.status = Some(Status::Burn);
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        assert_eq!(calculator.get_damage(100, false), 9);
+
+        let special_tackle = test_utils::make_move_entry("tackle", r#"category = "Special""#);
+        let calculator = DamageCalculator::new(&attacker, &defender, special_tackle);
+        assert_eq!(calculator.get_damage(100, false), 19);
+    }
+
+    #[test]
+    fn overgrow_boosts_grass_moves_at_low_hp() {
+        let (mut attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let vine_whip = get_global_move_entry(Name::VineWhip).unwrap();
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        attacker.current_hp = attacker.stats.hp / 3 + 1;
+        assert_eq!(
+            DamageCalculator::new(&attacker, &defender, vine_whip).get_damage(100, false),
+            31
+        );
+
+        attacker.current_hp = attacker.stats.hp / 3;
+        assert_eq!(
+            DamageCalculator::new(&attacker, &defender, vine_whip).get_damage(100, false),
+            46
+        );
+        assert_eq!(
+            DamageCalculator::new(&attacker, &defender, tackle).get_damage(100, false),
+            19
+        );
+    }
+
+    #[test]
+    fn critical_hits_depend_on_the_stage() {
+        assert_eq!(get_critical_hit_chance(0), 1. / 24.);
+        assert_eq!(get_critical_hit_chance(1), 1. / 8.);
+        assert_eq!(get_critical_hit_chance(2), 1. / 2.);
+        assert_eq!(get_critical_hit_chance(3), 1.);
+        assert_eq!(get_critical_hit_chance(6), 1.);
+
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let mut calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        calculator.critical_hit_stage = 3;
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!((0..10).all(|_| calculator.roll(&mut rng).is_critical));
+        assert_eq!(calculator.get_damage(100, true), 28);
+
+        // Critical hits ignore the lowered attack and the raised defense
+        calculator.attacker_stages.change(BattleStat::Attack, -2);
+        calculator.defender_stages.change(BattleStat::Defense, 2);
+        assert_eq!(calculator.get_damage(100, false), 6);
+        assert_eq!(calculator.get_damage(100, true), 28);
+    }
+
+    #[test]
+    fn stages_change_the_attack_and_the_defense() {
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let mut calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        calculator.attacker_stages.change(BattleStat::Attack, 2);
+        assert_eq!(calculator.get_damage(100, false), 37);
+        calculator.defender_stages.change(BattleStat::Defense, 2);
+        assert_eq!(calculator.get_damage(100, false), 19);
+        // Physical moves don't use the special stats
+        calculator
+            .attacker_stages
+            .change(BattleStat::SpecialAttack, 6);
+        assert_eq!(calculator.get_damage(100, false), 19);
+    }
+
+    #[test]
+    fn spread_moves_deal_less_damage() {
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        let mut calculator = DamageCalculator::new(&attacker, &defender, tackle);
+        calculator.is_spread = true;
+        assert_eq!(calculator.get_damage(100, false), 14);
+    }
+
+    #[test]
+    fn weather_boosts_or_weakens_fire_and_water() {
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let fire_tackle = test_utils::make_move_entry("tackle", r#"type = "Fire""#);
+        let mut calculator = DamageCalculator::new(&attacker, &defender, fire_tackle);
+        calculator.field.set_weather(Weather::Sun);
+        assert_eq!(calculator.get_damage(100, false), 28);
+        calculator.field.set_weather(Weather::Rain);
+        assert_eq!(calculator.get_damage(100, false), 9);
+
+        let water_tackle = test_utils::make_move_entry("tackle", r#"type = "Water""#);
+        let mut calculator = DamageCalculator::new(&attacker, &defender, water_tackle);
+        calculator.field.set_weather(Weather::Rain);
+        assert_eq!(calculator.get_damage(100, false), 28);
+        calculator.field.set_weather(Weather::Sand);
+        assert_eq!(calculator.get_damage(100, false), 19);
+    }
+
+    #[test]
+    fn weather_raises_the_defense_of_some_types() {
+        let attacker = make_pokemon();
+        let defender = make_defender(&[Type::Rock]);
+        let special_tackle = test_utils::make_move_entry("tackle", r#"category = "Special""#);
+        let mut calculator = DamageCalculator::new(&attacker, &defender, special_tackle);
+        assert_eq!(calculator.get_damage(100, false), 9);
+        calculator.field.set_weather(Weather::Sand);
+        assert_eq!(calculator.get_damage(100, false), 6);
+    }
+
+    #[test]
+    fn terrains_boost_the_moves_of_grounded_attackers() {
+        let (attacker, defender) = (make_pokemon(), make_defender(&[Type::Normal]));
+        let vine_whip = get_global_move_entry(Name::VineWhip).unwrap();
+        let mut calculator = DamageCalculator::new(&attacker, &defender, vine_whip);
+        calculator.field.set_terrain(Terrain::Grassy);
+        assert_eq!(calculator.get_damage(100, false), 40);
+
+        let flying_attacker = make_defender(&[Type::Grass, Type::Flying]);
+        let calculator = DamageCalculator {
+            attacker: &flying_attacker,
+            ..calculator
+        };
+        assert_eq!(calculator.get_damage(100, false), 31);
+    }
+}
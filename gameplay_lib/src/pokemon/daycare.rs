@@ -0,0 +1,338 @@
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    egg::Egg,
+    factory::PokemonFactory,
+    learnsets::get_global_learnsets,
+    moves::{self, keep_most_recent_moves, moveset::Moveset},
+    personality::{ShinyOdds, TrainerId},
+    pokedex::{
+        breeding::{EggGroup, Gender},
+        entry::PokedexEntry,
+        get_global_pokedex_entry,
+    },
+    stats::{StatType, Stats},
+    Pokemon,
+};
+
+/// Number of steps between two attempts at producing an egg
+pub const NB_STEPS_PER_EGG_ROLL: u8 = 0xFF;
+
+/// Number of IVs inherited from the parents
+pub const NB_INHERITED_IVS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Incompatible,
+    /// Different species or breeding with Ditto
+    Low,
+    /// Same species
+    Medium,
+}
+
+impl Compatibility {
+    /// Probability to produce an egg every `NB_STEPS_PER_EGG_ROLL` steps
+    pub fn get_egg_chance(&self) -> f64 {
+        match self {
+            Compatibility::Incompatible => 0.,
+            Compatibility::Low => 0.2,
+            Compatibility::Medium => 0.5,
+        }
+    }
+}
+
+fn get_egg_groups(entry: &PokedexEntry) -> Vec<EggGroup> {
+    let mut egg_groups = vec![entry.egg_group_1];
+    if let Some(egg_group_2) = entry.egg_group_2 {
+        egg_groups.push(egg_group_2);
+    }
+    egg_groups
+}
+
+fn is_ditto(pokemon: &Pokemon) -> bool {
+    get_egg_groups(pokemon.pokedex_entry).contains(&EggGroup::Ditto)
+}
+
+fn can_breed(pokemon: &Pokemon) -> bool {
+    !get_egg_groups(pokemon.pokedex_entry).contains(&EggGroup::NoEggsDiscovered)
+}
+
+/// Tells how likely two pokemons are to produce an egg together
+pub fn get_compatibility(parent_1: &Pokemon, parent_2: &Pokemon) -> Compatibility {
+    if !can_breed(parent_1) || !can_breed(parent_2) {
+        return Compatibility::Incompatible;
+    }
+    match (is_ditto(parent_1), is_ditto(parent_2)) {
+        (true, true) => return Compatibility::Incompatible,
+        (true, false) | (false, true) => return Compatibility::Low,
+        (false, false) => (),
+    }
+
+    let egg_groups_2 = get_egg_groups(parent_2.pokedex_entry);
+    let share_egg_group = get_egg_groups(parent_1.pokedex_entry)
+        .iter()
+        .any(|egg_group| egg_groups_2.contains(egg_group));
+    let have_opposite_genders = matches!(
+        (parent_1.gender, parent_2.gender),
+        (Gender::Male, Gender::Female) | (Gender::Female, Gender::Male)
+    );
+    if !share_egg_group || !have_opposite_genders {
+        Compatibility::Incompatible
+    } else if parent_1.pokedex_entry.pokedex_number == parent_2.pokedex_entry.pokedex_number {
+        Compatibility::Medium
+    } else {
+        Compatibility::Low
+    }
+}
+
+/// The lowest evolution of the given species
+fn get_base_species(entry: &'static PokedexEntry) -> Result<&'static PokedexEntry, ErrorCode> {
+    let mut base_entry = entry;
+    while let Some(previous_evolution) = base_entry.previous_evolution {
+        base_entry = get_global_pokedex_entry(previous_evolution)?;
+    }
+    Ok(base_entry)
+}
+
+/// Some IVs come from a random parent, the other ones are random
+fn get_inherited_ivs<R: Rng>(parent_1: &Pokemon, parent_2: &Pokemon, rng: &mut R) -> Stats {
+    let mut ivs = Stats::random_ivs(rng);
+    for stat_type in StatType::ALL.choose_multiple(rng, NB_INHERITED_IVS) {
+        let parent = if rng.gen_bool(0.5) {
+            parent_1
+        } else {
+            parent_2
+        };
+        ivs.set(*stat_type, parent.ivs.get(*stat_type));
+    }
+    ivs
+}
+
+/// The level 1 moves of the species followed by the egg moves known by the parents
+fn get_inherited_moves(
+    entry: &PokedexEntry,
+    parent_1: &Pokemon,
+    parent_2: &Pokemon,
+) -> Result<Vec<moves::names::Name>, ErrorCode> {
+    let learnsets = get_global_learnsets()?;
+    let learnset = learnsets.get(entry.name)?;
+
+    let level_up_moves = learnset.get_moves_learned_until_level(1);
+    let parent_moves = parent_1
+        .moveset
+        .get_names()
+        .into_iter()
+        .chain(parent_2.moveset.get_names())
+        .filter(|name| learnset.egg.contains(name));
+    Ok(keep_most_recent_moves(
+        level_up_moves.into_iter().chain(parent_moves),
+    ))
+}
+
+/// Creates the egg the two parents would produce for the given trainer
+pub fn make_egg<R: Rng>(
+    parent_1: &Pokemon,
+    parent_2: &Pokemon,
+    trainer: &TrainerId,
+    shiny_odds: &ShinyOdds,
+    rng: &mut R,
+) -> Result<Egg, ErrorCode> {
+    if get_compatibility(parent_1, parent_2) == Compatibility::Incompatible {
+        error!(
+            "The pokemons {} and {} can't produce an egg together",
+            parent_1.get_display_name(),
+            parent_2.get_display_name()
+        );
+        return Err(ErrorCode::BadValue);
+    }
+
+    let mother = if is_ditto(parent_1) || parent_2.gender == Gender::Female {
+        parent_2
+    } else {
+        parent_1
+    };
+    let entry = get_base_species(mother.pokedex_entry)?;
+
+    let factory = PokemonFactory::new(*trainer, *shiny_odds);
+    let mut pokemon = factory.generate(entry.pokedex_number, 1, rng)?;
+    pokemon.moveset = Moveset::new(&get_inherited_moves(entry, parent_1, parent_2)?)?;
+    pokemon.ivs = get_inherited_ivs(parent_1, parent_2, rng);
+    pokemon.update_stats();
+
+    Ok(Egg::new(pokemon))
+}
+
+/// A place where two pokemons can be left to produce eggs
+#[derive(Default)]
+pub struct Daycare {
+    pub pokemon_1: Option<Pokemon>,
+    pub pokemon_2: Option<Pokemon>,
+    /// The egg waiting to be collected
+    pub egg: Option<Egg>,
+    /// Number of steps walked since the last attempt at producing an egg
+    pub nb_steps: u8,
+}
+
+impl Daycare {
+    /// Returns the pokemon back if the daycare is already full
+    pub fn deposit(&mut self, pokemon: Pokemon) -> Option<Pokemon> {
+        if self.pokemon_1.is_none() {
+            self.pokemon_1 = Some(pokemon);
+        } else if self.pokemon_2.is_none() {
+            self.pokemon_2 = Some(pokemon);
+        } else {
+            return Some(pokemon);
+        }
+        self.nb_steps = 0;
+        None
+    }
+
+    /// Takes back the first or the second deposited pokemon
+    pub fn withdraw(&mut self, index: usize) -> Option<Pokemon> {
+        match index {
+            0 => self.pokemon_1.take(),
+            1 => self.pokemon_2.take(),
+            _ => None,
+        }
+    }
+
+    pub fn collect_egg(&mut self) -> Option<Egg> {
+        self.egg.take()
+    }
+
+    pub fn get_compatibility(&self) -> Compatibility {
+        match (&self.pokemon_1, &self.pokemon_2) {
+            (Some(parent_1), Some(parent_2)) => get_compatibility(parent_1, parent_2),
+            _ => Compatibility::Incompatible,
+        }
+    }
+
+    /// Runs every time the given trainer walks a step
+    /// Returns true if a new egg is waiting to be collected
+    pub fn on_step<R: Rng>(
+        &mut self,
+        trainer: &TrainerId,
+        shiny_odds: &ShinyOdds,
+        rng: &mut R,
+    ) -> bool {
+        if self.egg.is_some() {
+            return false;
+        }
+        self.nb_steps += 1;
+        if self.nb_steps != NB_STEPS_PER_EGG_ROLL {
+            return false;
+        }
+        self.nb_steps = 0;
+
+        let (parent_1, parent_2) = match (&self.pokemon_1, &self.pokemon_2) {
+            (Some(parent_1), Some(parent_2)) => (parent_1, parent_2),
+            _ => return false,
+        };
+        let egg_chance = get_compatibility(parent_1, parent_2).get_egg_chance();
+        if egg_chance <= 0. || !rng.gen_bool(egg_chance) {
+            return false;
+        }
+        match make_egg(parent_1, parent_2, trainer, shiny_odds, rng) {
+            Ok(egg) => {
+                self.egg = Some(egg);
+                true
+            }
+            Err(err) => {
+                error!("Failed to produce an egg in the daycare: {:?}", err);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{
+        moves::names::Name,
+        personality::{ShinyOdds, TrainerId},
+        pokedex::{breeding::Gender, Id},
+        stats::MAX_IV,
+        test_utils::make_pokemon,
+        Pokemon,
+    };
+
+    use super::{get_compatibility, make_egg, Compatibility, Daycare};
+
+    /// A female pokemon of the given species and level
+    fn make_female(id: Id, level: u8) -> Pokemon {
+        let mut pokemon = make_pokemon(id, level);
+        pokemon.gender = Gender::Female;
+        pokemon
+    }
+
+    #[test]
+    fn same_species_are_more_compatible() {
+        let bulbasaur = make_pokemon(1, 20);
+        assert_eq!(
+            get_compatibility(&bulbasaur, &make_female(1, 5)),
+            Compatibility::Medium
+        );
+        assert_eq!(
+            get_compatibility(&bulbasaur, &make_female(2, 20)),
+            Compatibility::Low
+        );
+        assert_eq!(Compatibility::Medium.get_egg_chance(), 0.5);
+    }
+
+    #[test]
+    fn parents_need_opposite_genders() {
+        let bulbasaur = make_pokemon(1, 20);
+        assert_eq!(
+            get_compatibility(&bulbasaur, &make_pokemon(1, 5)),
+            Compatibility::Incompatible
+        );
+        let mut genderless = make_female(1, 5);
+        genderless.gender = Gender::Neutral;
+        assert_eq!(
+            get_compatibility(&bulbasaur, &genderless),
+            Compatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn eggs_contain_the_base_species() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let egg = make_egg(
+            &make_pokemon(3, 50),
+            &make_female(2, 30),
+            &TrainerId::default(),
+            &ShinyOdds::default(),
+            &mut rng,
+        )
+        .unwrap();
+        let pokemon = &egg.pokemon;
+        assert_eq!(pokemon.pokedex_entry.pokedex_number, 1);
+        assert_eq!(pokemon.level, 1);
+        assert_eq!(pokemon.moveset.get_names(), [Name::Tackle, Name::Growl]);
+        assert!(pokemon.ivs.total() <= 6 * MAX_IV);
+        assert_eq!(egg.nb_cycles_left, pokemon.pokedex_entry.hatch_time);
+    }
+
+    #[test]
+    fn walking_eventually_produces_an_egg() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (trainer, shiny_odds) = (TrainerId::default(), ShinyOdds::default());
+        let mut daycare = Daycare::default();
+        assert!(daycare.deposit(make_pokemon(1, 10)).is_none());
+        assert!(daycare.deposit(make_female(1, 12)).is_none());
+        assert!(daycare.deposit(make_pokemon(1, 14)).is_some());
+
+        let nb_steps = (0..100_000)
+            .position(|_| daycare.on_step(&trainer, &shiny_odds, &mut rng))
+            .unwrap();
+        assert_eq!((nb_steps + 1) % super::NB_STEPS_PER_EGG_ROLL as usize, 0);
+        // No other egg is produced until this one is collected
+        assert!((0..10_000).all(|_| !daycare.on_step(&trainer, &shiny_odds, &mut rng)));
+        assert!(daycare.collect_egg().is_some());
+        assert!(daycare.egg.is_none());
+    }
+}
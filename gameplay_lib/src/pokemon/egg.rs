@@ -1,6 +1,60 @@
-use super::Pokemon;
+use super::{pokedex::breeding::NB_STEPS_PER_EGG_CYCLE, Pokemon};
 
 pub struct Egg {
     pub pokemon: Pokemon,
     pub nb_cycles_left: u8,
+    /// Number of steps walked during the current egg cycle
+    pub nb_steps: u8,
+}
+
+impl Egg {
+    pub fn new(pokemon: Pokemon) -> Self {
+        let nb_cycles_left = pokemon.pokedex_entry.hatch_time;
+        Self {
+            pokemon,
+            nb_cycles_left,
+            nb_steps: 0,
+        }
+    }
+
+    pub fn is_ready_to_hatch(&self) -> bool {
+        self.nb_cycles_left == 0
+    }
+
+    /// Runs every time the player walks a step with the egg
+    /// Returns true if the egg is ready to hatch
+    pub fn on_step(&mut self) -> bool {
+        if self.is_ready_to_hatch() {
+            return true;
+        }
+        self.nb_steps += 1;
+        if self.nb_steps == NB_STEPS_PER_EGG_CYCLE {
+            self.nb_steps = 0;
+            self.nb_cycles_left -= 1;
+        }
+        self.is_ready_to_hatch()
+    }
+
+    pub fn hatch(self) -> Pokemon {
+        self.pokemon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{pokedex::breeding::NB_STEPS_PER_EGG_CYCLE, test_utils::make_pokemon};
+
+    use super::Egg;
+
+    #[test]
+    fn eggs_hatch_after_their_cycles() {
+        let mut egg = Egg::new(make_pokemon(1, 1));
+        let nb_steps = egg.nb_cycles_left as usize * NB_STEPS_PER_EGG_CYCLE as usize;
+        for _ in 1..nb_steps {
+            assert!(!egg.on_step());
+        }
+        assert!(egg.on_step());
+        assert!(egg.is_ready_to_hatch());
+        assert_eq!(egg.hatch().pokedex_entry.pokedex_number, 1);
+    }
 }
@@ -0,0 +1,185 @@
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+use crate::pokemon::{
+    learnsets::get_global_learnsets,
+    moves,
+    pokedex::{get_global_pokedex_entry, Id},
+    Pokemon,
+};
+
+use super::{EvolutionCondition, EvolutionType, TimeOfDay};
+
+/// The state of the world when checking for an evolution
+#[derive(Debug, Clone, Copy)]
+pub struct EvolutionContext<'a> {
+    pub time_of_day: TimeOfDay,
+    pub location: Option<&'a str>,
+}
+
+/// An evolution about to happen
+/// The UI can cancel it by simply dropping it instead of calling `Pokemon::evolve`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvolutionEvent {
+    pub from: Id,
+    pub into: Id,
+    /// Tells if the held item is consumed by the evolution
+    pub consumes_held_item: bool,
+}
+
+impl Pokemon {
+    fn is_condition_met(&self, condition: &EvolutionCondition, context: &EvolutionContext) -> bool {
+        match condition {
+            EvolutionCondition::MinimumLevel(level) => self.level >= *level,
+            EvolutionCondition::HeldItem(item) => self.held_item.as_ref() == Some(item),
+            EvolutionCondition::MinimumFriendship(friendship) => self.friendship >= *friendship,
+            EvolutionCondition::TimeOfDay(time_of_day) => context.time_of_day == *time_of_day,
+            EvolutionCondition::KnownMove(name) => self.moveset.contains(*name),
+            EvolutionCondition::Gender(gender) => self.gender == *gender,
+            EvolutionCondition::Location(location) => context.location == Some(location.as_str()),
+        }
+    }
+
+    /// Looks for the first evolution started by the given trigger whose conditions all hold
+    pub fn check_evolution(
+        &self,
+        trigger: &EvolutionType,
+        context: &EvolutionContext,
+    ) -> Option<EvolutionEvent> {
+        self.pokedex_entry
+            .evolutions
+            .iter()
+            .find(|evolution| {
+                evolution.evolution_type == *trigger
+                    && evolution
+                        .conditions
+                        .iter()
+                        .all(|condition| self.is_condition_met(condition, context))
+            })
+            .map(|evolution| EvolutionEvent {
+                from: self.pokedex_entry.pokedex_number,
+                into: evolution.into,
+                consumes_held_item: evolution
+                    .conditions
+                    .iter()
+                    .any(|condition| matches!(condition, EvolutionCondition::HeldItem(_))),
+            })
+    }
+
+    /// Transforms the pokemon into its evolution
+    /// Everything but the species, the default nickname and the stats is kept
+    /// Returns the moves learned on evolution
+    pub fn evolve(&mut self, event: &EvolutionEvent) -> Result<Vec<moves::names::Name>, ErrorCode> {
+        if event.from != self.pokedex_entry.pokedex_number {
+            error!(
+                "Can't evolve the pokemon #{:0>4} with an evolution from #{:0>4}",
+                self.pokedex_entry.pokedex_number, event.from
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        let is_evolution_edge = self
+            .pokedex_entry
+            .evolutions
+            .iter()
+            .any(|evolution| evolution.into == event.into);
+        if !is_evolution_edge {
+            error!(
+                "The pokemon #{:0>4} can't evolve into #{:0>4}",
+                self.pokedex_entry.pokedex_number, event.into
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        let new_entry = get_global_pokedex_entry(event.into)?;
+
+        if self.nickname == format!("{:?}", self.pokedex_entry.name) {
+            self.nickname = format!("{:?}", new_entry.name);
+        }
+        if event.consumes_held_item {
+            self.held_item = None;
+        }
+        self.pokedex_entry = new_entry;
+        self.update_stats();
+
+        // Moves at level 0 are learned on evolution
+        let learnsets = get_global_learnsets()?;
+        let new_moves = match learnsets.data.get(&new_entry.name) {
+            Some(learnset) => {
+                let mut new_moves = learnset.get_moves_learned_at_level(0);
+                new_moves.extend(learnset.get_moves_learned_at_level(self.level));
+                new_moves
+            }
+            None => Vec::new(),
+        };
+        Ok(new_moves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{
+        evolutions::{EvolutionType, TimeOfDay},
+        test_utils::make_pokemon,
+    };
+
+    use super::{EvolutionContext, EvolutionEvent};
+
+    const CONTEXT: EvolutionContext = EvolutionContext {
+        time_of_day: TimeOfDay::Day,
+        location: None,
+    };
+
+    #[test]
+    fn pokemons_evolve_once_the_conditions_hold() {
+        let mut bulbasaur = make_pokemon(1, 15);
+        assert_eq!(
+            bulbasaur.check_evolution(&EvolutionType::LevelUp, &CONTEXT),
+            None
+        );
+        assert_eq!(
+            bulbasaur.check_evolution(&EvolutionType::Trade, &CONTEXT),
+            None
+        );
+
+        bulbasaur.set_level(16);
+        let event = bulbasaur.check_evolution(&EvolutionType::LevelUp, &CONTEXT);
+        assert_eq!(
+            event,
+            Some(EvolutionEvent {
+                from: 1,
+                into: 2,
+                consumes_held_item: false,
+            })
+        );
+    }
+
+    #[test]
+    fn evolving_changes_the_species_and_the_stats() {
+        let mut pokemon = make_pokemon(1, 16);
+        let hp = pokemon.stats.hp;
+        let event = pokemon
+            .check_evolution(&EvolutionType::LevelUp, &CONTEXT)
+            .unwrap();
+        pokemon.evolve(&event).unwrap();
+        assert_eq!(pokemon.pokedex_entry.pokedex_number, 2);
+        assert_eq!(pokemon.nickname, "Ivysaur");
+        assert!(pokemon.stats.hp > hp);
+    }
+
+    #[test]
+    fn only_the_evolutions_of_the_species_are_allowed() {
+        let mut pokemon = make_pokemon(1, 50);
+        let skipping_a_stage = EvolutionEvent {
+            from: 1,
+            into: 3,
+            consumes_held_item: false,
+        };
+        assert!(pokemon.evolve(&skipping_a_stage).is_err());
+        let from_another_species = EvolutionEvent {
+            from: 2,
+            into: 3,
+            consumes_held_item: false,
+        };
+        assert!(pokemon.evolve(&from_another_species).is_err());
+        assert_eq!(pokemon.pokedex_entry.pokedex_number, 1);
+    }
+}
@@ -1,23 +1,201 @@
+use std::str::FromStr;
+
 use core_lib::utils::{debug::ErrorCode, toml::Toml};
 use log::error;
 
-#[derive(Debug)]
+use super::{
+    moves,
+    pokedex::{breeding::Gender, Id},
+};
+
+pub mod engine;
+
+/// The event starting an evolution
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EvolutionType {
-    LevelUp(u8),
+    LevelUp,
+    UseItem(String),
+    Trade,
 }
 
-impl EvolutionType {
-    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Morning,
+    Day,
+    Evening,
+    Night,
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ErrorCode;
+
+    fn from_str(time_of_day: &str) -> Result<Self, ErrorCode> {
+        match time_of_day {
+            "Morning" => Ok(TimeOfDay::Morning),
+            "Day" => Ok(TimeOfDay::Day),
+            "Evening" => Ok(TimeOfDay::Evening),
+            "Night" => Ok(TimeOfDay::Night),
+            _ => {
+                error!("The time of day {} is not valid", time_of_day);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// A condition which must hold for an evolution to happen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvolutionCondition {
+    MinimumLevel(u8),
+    HeldItem(String),
+    MinimumFriendship(u8),
+    TimeOfDay(TimeOfDay),
+    KnownMove(moves::names::Name),
+    Gender(Gender),
+    Location(String),
+}
+
+/// An edge from a species to one of its evolutions
+/// Every condition must hold when the trigger happens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evolution {
+    pub into: Id,
+    pub evolution_type: EvolutionType,
+    pub conditions: Vec<EvolutionCondition>,
+}
+
+impl Evolution {
+    fn get_evolution_type(toml: &toml::Table) -> Result<EvolutionType, ErrorCode> {
         let evolution_type = Toml::get_string(toml, "type")?;
         match evolution_type.as_str() {
-            "LevelUp" => {
-                let level = Toml::get_u8(toml, "level")?;
-                Ok(Self::LevelUp(level))
-            },
+            "LevelUp" => Ok(EvolutionType::LevelUp),
+            "UseItem" => {
+                let item = Toml::get_string(toml, "item")?;
+                Ok(EvolutionType::UseItem(item))
+            }
+            "Trade" => Ok(EvolutionType::Trade),
             _ => {
                 error!("The type {} is not a valid evolution type", evolution_type);
                 Err(ErrorCode::BadValue)
             }
         }
     }
-}
\ No newline at end of file
+
+    fn get_conditions(toml: &toml::Table) -> Result<Vec<EvolutionCondition>, ErrorCode> {
+        let mut conditions = Vec::new();
+        if toml.contains_key("level") {
+            let level = Toml::get_u8(toml, "level")?;
+            conditions.push(EvolutionCondition::MinimumLevel(level));
+        }
+        if toml.contains_key("held_item") {
+            let item = Toml::get_string(toml, "held_item")?;
+            conditions.push(EvolutionCondition::HeldItem(item));
+        }
+        if toml.contains_key("friendship") {
+            let friendship = Toml::get_u8(toml, "friendship")?;
+            conditions.push(EvolutionCondition::MinimumFriendship(friendship));
+        }
+        if toml.contains_key("time_of_day") {
+            let time_of_day = Toml::get_string(toml, "time_of_day")?;
+            conditions.push(EvolutionCondition::TimeOfDay(TimeOfDay::from_str(
+                &time_of_day,
+            )?));
+        }
+        if toml.contains_key("move") {
+            let name = Toml::get_string(toml, "move")?;
+            conditions.push(EvolutionCondition::KnownMove(moves::names::Name::from_str(
+                &name,
+            )?));
+        }
+        if toml.contains_key("gender") {
+            let gender = Toml::get_string(toml, "gender")?;
+            conditions.push(EvolutionCondition::Gender(Gender::from_str(&gender)?));
+        }
+        if toml.contains_key("location") {
+            let location = Toml::get_string(toml, "location")?;
+            conditions.push(EvolutionCondition::Location(location));
+        }
+        Ok(conditions)
+    }
+
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let into = Toml::get_u16(toml, "into")? as Id;
+        let evolution_type = Self::get_evolution_type(toml)?;
+        let conditions = Self::get_conditions(toml)?;
+
+        Ok(Self {
+            into,
+            evolution_type,
+            conditions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{moves, pokedex::breeding::Gender};
+
+    use super::{Evolution, EvolutionCondition, EvolutionType, TimeOfDay};
+
+    fn make_evolution(toml: &str) -> Result<Evolution, core_lib::utils::debug::ErrorCode> {
+        Evolution::from_toml(&toml.parse::<toml::Table>().unwrap())
+    }
+
+    #[test]
+    fn every_condition_is_combined() {
+        let evolution = make_evolution(
+            r#"
+            into = 196
+            type = "LevelUp"
+            level = 20
+            friendship = 160
+            time_of_day = "Day"
+            move = "Tackle"
+            gender = "Female"
+            location = "Route 217"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(evolution.into, 196);
+        assert_eq!(evolution.evolution_type, EvolutionType::LevelUp);
+        assert_eq!(
+            evolution.conditions,
+            [
+                EvolutionCondition::MinimumLevel(20),
+                EvolutionCondition::MinimumFriendship(160),
+                EvolutionCondition::TimeOfDay(TimeOfDay::Day),
+                EvolutionCondition::KnownMove(moves::names::Name::Tackle),
+                EvolutionCondition::Gender(Gender::Female),
+                EvolutionCondition::Location("Route 217".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn items_are_read_with_their_trigger() {
+        let evolution = make_evolution(
+            r#"
+            into = 3
+            type = "UseItem"
+            item = "Leaf Stone"
+            held_item = "Everstone"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            evolution.evolution_type,
+            EvolutionType::UseItem("Leaf Stone".to_string())
+        );
+        assert_eq!(
+            evolution.conditions,
+            [EvolutionCondition::HeldItem("Everstone".to_string())]
+        );
+    }
+
+    #[test]
+    fn invalid_evolutions_are_rejected() {
+        assert!(make_evolution("into = 2\ntype = \"Levelup\"").is_err());
+        assert!(make_evolution("into = 2\ntype = \"UseItem\"").is_err());
+        assert!(make_evolution("into = 2\ntype = \"Trade\"\ntime_of_day = \"Noon\"").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+use rand::Rng;
+
+use super::{
+    learnsets::get_global_learnsets,
+    moves::{keep_most_recent_moves, moveset::Moveset},
+    natures::get_global_natures,
+    personality::{
+        get_ability_slot, get_nature_name, roll_personality_value, ShinyOdds, TrainerId,
+    },
+    pokedex::{experience_group::MAX_LEVEL, get_global_pokedex_entry, Id},
+    stats::Stats,
+    Pokemon,
+};
+
+/// Generates complete pokemons for a given trainer
+/// Used for wild encounters, gifts and eggs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PokemonFactory {
+    pub trainer: TrainerId,
+    pub shiny_odds: ShinyOdds,
+}
+
+impl PokemonFactory {
+    pub fn new(trainer: TrainerId, shiny_odds: ShinyOdds) -> Self {
+        Self {
+            trainer,
+            shiny_odds,
+        }
+    }
+
+    /// Creates a pokemon of the given species at the given level
+    /// It knows the last four moves it could have learned by leveling up
+    pub fn generate<R: Rng>(
+        &self,
+        species: Id,
+        level: u8,
+        rng: &mut R,
+    ) -> Result<Pokemon, ErrorCode> {
+        if level == 0 || level > MAX_LEVEL {
+            error!(
+                "Can't generate a pokemon at level {}, it must be between 1 and {}",
+                level, MAX_LEVEL
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        let entry = get_global_pokedex_entry(species)?;
+
+        let learnsets = get_global_learnsets()?;
+        let names = keep_most_recent_moves(
+            learnsets
+                .get(entry.name)?
+                .get_moves_learned_until_level(level),
+        );
+        if names.is_empty() {
+            error!(
+                "The species {:?} knows no move at level {}",
+                entry.name, level
+            );
+            return Err(ErrorCode::NotFound);
+        }
+        let moveset = Moveset::new(&names)?;
+
+        let personality_value = roll_personality_value(rng, &self.trainer, &self.shiny_odds);
+        let nature = get_global_natures()?.get(get_nature_name(personality_value))?;
+        let ability = match entry
+            .abilities
+            .get(get_ability_slot(personality_value))
+            .or(entry.abilities.first())
+        {
+            Some(ability) => *ability,
+            None => {
+                error!("The species {:?} has no ability", entry.name);
+                return Err(ErrorCode::NotFound);
+            }
+        };
+
+        let mut pokemon = Pokemon {
+            pokedex_entry: entry,
+            nickname: format!("{:?}", entry.name),
+            id: entry.pokedex_number as super::Id,
+            gender: entry.gender_ratio.get_gender(personality_value),
+            ability,
+            personality_value,
+            original_trainer: self.trainer,
+            held_item: None,
+            friendship: entry.base_friendship,
+            level,
+            experience: 0,
+            nature,
+            moveset,
+            evs: Stats::default(),
+            ivs: Stats::random_ivs(rng),
+            stats: Stats::default(),
+            current_hp: 0,
+            status: None,
+        };
+        pokemon.set_level(level);
+
+        Ok(pokemon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{moves::names::Name, personality::TrainerId, stats::MAX_IV};
+
+    use super::PokemonFactory;
+
+    #[test]
+    fn pokemons_know_their_most_recent_moves() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let pokemon = PokemonFactory::default().generate(1, 10, &mut rng).unwrap();
+        assert_eq!(pokemon.level, 10);
+        assert_eq!(
+            pokemon.moveset.get_names(),
+            [Name::Growl, Name::VineWhip, Name::Growth, Name::LeechSeed]
+        );
+        assert_eq!(pokemon.friendship, pokemon.pokedex_entry.base_friendship);
+        assert!(pokemon.ivs.attack <= MAX_IV);
+    }
+
+    #[test]
+    fn pokemons_belong_to_the_factory_trainer() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let trainer = TrainerId {
+            id: 12345,
+            secret_id: 54321,
+        };
+        let factory = PokemonFactory::new(trainer, Default::default());
+        let pokemon = factory.generate(2, 20, &mut rng).unwrap();
+        assert_eq!(pokemon.original_trainer, trainer);
+    }
+
+    #[test]
+    fn levels_out_of_range_are_rejected() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(PokemonFactory::default().generate(1, 0, &mut rng).is_err());
+        assert!(PokemonFactory::default()
+            .generate(1, 101, &mut rng)
+            .is_err());
+        assert!(PokemonFactory::default()
+            .generate(9999, 5, &mut rng)
+            .is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use std::str::FromStr;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+use super::{types::Type, Pokemon};
+
+/// Number of turns a weather or a terrain lasts
+pub const FIELD_CONDITION_DURATION: u8 = 5;
+
+/// Battle-wide weather
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weather {
+    Sun,
+    Rain,
+    Sand,
+    Snow,
+}
+
+impl Weather {
+    /// Type taken by Weather Ball
+    pub fn get_type(&self) -> Type {
+        match self {
+            Weather::Sun => Type::Fire,
+            Weather::Rain => Type::Water,
+            Weather::Sand => Type::Rock,
+            Weather::Snow => Type::Ice,
+        }
+    }
+
+    /// Tells if the pokemon is hurt at the end of every turn
+    pub fn hurts(&self, pokemon: &Pokemon) -> bool {
+        let entry = pokemon.pokedex_entry;
+        match self {
+            Weather::Sand => {
+                !entry.has_type(&Type::Rock)
+                    && !entry.has_type(&Type::Ground)
+                    && !entry.has_type(&Type::Steel)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Weather {
+    type Err = ErrorCode;
+
+    fn from_str(weather: &str) -> Result<Self, ErrorCode> {
+        match weather {
+            "Sun" => Ok(Weather::Sun),
+            "Rain" => Ok(Weather::Rain),
+            "Sand" => Ok(Weather::Sand),
+            "Snow" => Ok(Weather::Snow),
+            _ => {
+                error!("The weather {} is not a valid weather", weather);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// Battle-wide terrain, only affects the grounded pokemons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terrain {
+    /// Heals a little every turn
+    Grassy,
+    /// Prevents sleep
+    Electric,
+    /// Prevents moves with a raised priority
+    Psychic,
+    /// Prevents every status
+    Misty,
+}
+
+impl Terrain {
+    /// Type of the moves powered up by the terrain
+    pub fn get_boosted_type(&self) -> Option<Type> {
+        match self {
+            Terrain::Grassy => Some(Type::Grass),
+            Terrain::Electric => Some(Type::Electric),
+            Terrain::Psychic => Some(Type::Psychic),
+            Terrain::Misty => None,
+        }
+    }
+}
+
+impl FromStr for Terrain {
+    type Err = ErrorCode;
+
+    fn from_str(terrain: &str) -> Result<Self, ErrorCode> {
+        match terrain {
+            "Grassy" => Ok(Terrain::Grassy),
+            "Electric" => Ok(Terrain::Electric),
+            "Psychic" => Ok(Terrain::Psychic),
+            "Misty" => Ok(Terrain::Misty),
+            _ => {
+                error!("The terrain {} is not a valid terrain", terrain);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// Conditions shared by every pokemon in the battle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Field {
+    weather: Option<Weather>,
+    weather_turns_left: u8,
+    terrain: Option<Terrain>,
+    terrain_turns_left: u8,
+}
+
+impl Field {
+    pub fn get_weather(&self) -> Option<Weather> {
+        self.weather
+    }
+
+    pub fn get_weather_turns_left(&self) -> u8 {
+        self.weather_turns_left
+    }
+
+    pub fn get_terrain(&self) -> Option<Terrain> {
+        self.terrain
+    }
+
+    pub fn get_terrain_turns_left(&self) -> u8 {
+        self.terrain_turns_left
+    }
+
+    /// Fails if the weather is already active
+    pub fn set_weather(&mut self, weather: Weather) -> bool {
+        if self.weather == Some(weather) {
+            return false;
+        }
+        self.weather = Some(weather);
+        self.weather_turns_left = FIELD_CONDITION_DURATION;
+        true
+    }
+
+    pub fn clear_weather(&mut self) {
+        self.weather = None;
+        self.weather_turns_left = 0;
+    }
+
+    /// Fails if the terrain is already active
+    pub fn set_terrain(&mut self, terrain: Terrain) -> bool {
+        if self.terrain == Some(terrain) {
+            return false;
+        }
+        self.terrain = Some(terrain);
+        self.terrain_turns_left = FIELD_CONDITION_DURATION;
+        true
+    }
+
+    /// Terrain affecting the given pokemon
+    pub fn get_terrain_for(&self, pokemon: &Pokemon) -> Option<Terrain> {
+        self.terrain.filter(|_| pokemon.is_grounded())
+    }
+
+    /// Counts down the turns left
+    /// Returns the weather and the terrain which just ended
+    pub fn on_end_of_turn(&mut self) -> (Option<Weather>, Option<Terrain>) {
+        let mut ended_weather = None;
+        if self.weather.is_some() {
+            self.weather_turns_left = self.weather_turns_left.saturating_sub(1);
+            if self.weather_turns_left == 0 {
+                ended_weather = self.weather.take();
+            }
+        }
+        let mut ended_terrain = None;
+        if self.terrain.is_some() {
+            self.terrain_turns_left = self.terrain_turns_left.saturating_sub(1);
+            if self.terrain_turns_left == 0 {
+                ended_terrain = self.terrain.take();
+            }
+        }
+        (ended_weather, ended_terrain)
+    }
+}
+
+impl Pokemon {
+    /// Flying pokemons aren't affected by the terrain
+    pub fn is_grounded(&self) -> bool {
+        !self.pokedex_entry.has_type(&Type::Flying)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{test_utils, types::Type};
+
+    use super::{Field, Terrain, Weather, FIELD_CONDITION_DURATION};
+
+    #[test]
+    fn conditions_last_five_turns() {
+        let mut field = Field::default();
+        assert!(field.set_weather(Weather::Rain));
+        assert!(field.set_terrain(Terrain::Grassy));
+        assert_eq!(field.get_weather_turns_left(), FIELD_CONDITION_DURATION);
+        assert_eq!(field.get_terrain_turns_left(), FIELD_CONDITION_DURATION);
+
+        for turns_left in (1..FIELD_CONDITION_DURATION).rev() {
+            assert_eq!(field.on_end_of_turn(), (None, None));
+            assert_eq!(field.get_weather_turns_left(), turns_left);
+        }
+        assert_eq!(
+            field.on_end_of_turn(),
+            (Some(Weather::Rain), Some(Terrain::Grassy))
+        );
+        assert_eq!(field, Field::default());
+    }
+
+    #[test]
+    fn setting_the_active_condition_again_fails() {
+        let mut field = Field::default();
+        field.set_weather(Weather::Sun);
+        field.on_end_of_turn();
+        assert!(!field.set_weather(Weather::Sun));
+        assert_eq!(field.get_weather_turns_left(), FIELD_CONDITION_DURATION - 1);
+
+        // Another weather replaces it for the full duration
+        assert!(field.set_weather(Weather::Sand));
+        assert_eq!(field.get_weather(), Some(Weather::Sand));
+        assert_eq!(field.get_weather_turns_left(), FIELD_CONDITION_DURATION);
+
+        field.clear_weather();
+        assert_eq!(field.get_weather(), None);
+        assert_eq!(field.on_end_of_turn(), (None, None));
+    }
+
+    #[test]
+    fn terrains_only_affect_grounded_pokemons() {
+        let mut pokemon = test_utils::make_pokemon(1, 50);
+        let mut field = Field::default();
+        field.set_terrain(Terrain::Misty);
+        assert!(pokemon.is_grounded());
+        assert_eq!(field.get_terrain_for(&pokemon), Some(Terrain::Misty));
+
+        pokemon.pokedex_entry = test_utils::make_pokedex_entry(&[Type::Normal, Type::Flying]);
+        assert!(!pokemon.is_grounded());
+        assert_eq!(field.get_terrain_for(&pokemon), None);
+    }
+
+    #[test]
+    fn sand_spares_rock_ground_and_steel_types() {
+        let mut pokemon = test_utils::make_pokemon(1, 50);
+        assert!(Weather::Sand.hurts(&pokemon));
+        assert!(!Weather::Rain.hurts(&pokemon));
+        for element_type in [Type::Rock, Type::Ground, Type::Steel] {
+            pokemon.pokedex_entry = test_utils::make_pokedex_entry(&[Type::Water, element_type]);
+            assert!(!Weather::Sand.hurts(&pokemon));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use super::Pokemon;
+
+pub const MAX_FRIENDSHIP: u8 = 255;
+
+/// Minimum friendship usually required by friendship evolutions
+pub const FRIENDSHIP_EVOLUTION_THRESHOLD: u8 = 220;
+
+/// Number of steps the player walks between two friendship gains
+pub const NB_STEPS_PER_FRIENDSHIP_GAIN: u8 = 128;
+
+/// Held item boosting every friendship gain
+pub const SOOTHE_BELL: &str = "SootheBell";
+
+/// The gains and losses depend on how friendly the pokemon already is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FriendshipBand {
+    /// From 0 to 99
+    Low,
+    /// From 100 to 199
+    Medium,
+    /// From 200 to 255
+    High,
+}
+
+impl FriendshipBand {
+    pub fn from_friendship(friendship: u8) -> Self {
+        match friendship {
+            0..=99 => FriendshipBand::Low,
+            100..=199 => FriendshipBand::Medium,
+            _ => FriendshipBand::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FriendshipEvent {
+    /// Every `NB_STEPS_PER_FRIENDSHIP_GAIN` steps walked in the party
+    Walking,
+    LevelUp,
+    /// HP Up, Protein, Iron, Calcium, Zinc or Carbos
+    Vitamin,
+    Fainted,
+    /// Energy Powder or Heal Powder
+    BitterPowder,
+    EnergyRoot,
+    RevivalHerb,
+}
+
+impl FriendshipEvent {
+    /// Change of friendship for each band, from low to high
+    fn get_changes(&self) -> [i16; 3] {
+        match self {
+            FriendshipEvent::Walking => [1, 1, 1],
+            FriendshipEvent::LevelUp => [5, 3, 2],
+            FriendshipEvent::Vitamin => [5, 3, 2],
+            FriendshipEvent::Fainted => [-1, -1, -1],
+            FriendshipEvent::BitterPowder => [-5, -5, -10],
+            FriendshipEvent::EnergyRoot => [-10, -10, -15],
+            FriendshipEvent::RevivalHerb => [-15, -15, -20],
+        }
+    }
+
+    pub fn get_change(&self, band: FriendshipBand) -> i16 {
+        let changes = self.get_changes();
+        match band {
+            FriendshipBand::Low => changes[0],
+            FriendshipBand::Medium => changes[1],
+            FriendshipBand::High => changes[2],
+        }
+    }
+}
+
+/// Multiplies the friendship gains, losses are never modified
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FriendshipModifier {
+    pub gain_multiplier: f32,
+}
+
+impl FriendshipModifier {
+    pub fn new(gain_multiplier: f32) -> Self {
+        Self { gain_multiplier }
+    }
+
+    /// Boost given by the held item of the pokemon
+    pub fn from_pokemon(pokemon: &Pokemon) -> Self {
+        match pokemon.held_item.as_deref() {
+            Some(SOOTHE_BELL) => Self::new(1.5),
+            _ => Self::new(1.),
+        }
+    }
+
+    pub fn apply(&self, change: i16) -> i16 {
+        if change > 0 {
+            (change as f32 * self.gain_multiplier) as i16
+        } else {
+            change
+        }
+    }
+}
+
+impl Pokemon {
+    pub fn get_friendship_band(&self) -> FriendshipBand {
+        FriendshipBand::from_friendship(self.friendship)
+    }
+
+    /// Updates the friendship after the given event
+    /// Returns the friendship actually gained or lost
+    pub fn on_friendship_event(&mut self, event: FriendshipEvent) -> i16 {
+        let modifier = FriendshipModifier::from_pokemon(self);
+        let change = modifier.apply(event.get_change(self.get_friendship_band()));
+        let old_friendship = self.friendship;
+        self.friendship = (self.friendship as i16 + change).clamp(0, MAX_FRIENDSHIP as i16) as u8;
+        self.friendship as i16 - old_friendship as i16
+    }
+
+    /// Tells if the pokemon is friendly enough for a friendship evolution
+    pub fn has_high_friendship(&self) -> bool {
+        self.friendship >= FRIENDSHIP_EVOLUTION_THRESHOLD
+    }
+
+    /// Power of Return, grows with the friendship
+    pub fn get_return_power(&self) -> u8 {
+        (self.friendship as u16 * 10 / 25).max(1) as u8
+    }
+
+    /// Power of Frustration, grows as the friendship decreases
+    pub fn get_frustration_power(&self) -> u8 {
+        ((MAX_FRIENDSHIP - self.friendship) as u16 * 10 / 25).max(1) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::test_utils::make_pokemon;
+
+    use super::{FriendshipBand, FriendshipEvent, MAX_FRIENDSHIP, SOOTHE_BELL};
+
+    #[test]
+    fn changes_depend_on_the_band() {
+        let mut pokemon = make_pokemon(1, 5);
+        let expected = [(70, 5), (150, 3), (220, 2), (254, 1)];
+        for (friendship, change) in expected {
+            pokemon.friendship = friendship;
+            assert_eq!(
+                pokemon.on_friendship_event(FriendshipEvent::LevelUp),
+                change
+            );
+        }
+        assert_eq!(pokemon.friendship, MAX_FRIENDSHIP);
+
+        pokemon.friendship = 210;
+        assert_eq!(pokemon.get_friendship_band(), FriendshipBand::High);
+        assert_eq!(
+            pokemon.on_friendship_event(FriendshipEvent::RevivalHerb),
+            -20
+        );
+        pokemon.friendship = 3;
+        assert_eq!(pokemon.on_friendship_event(FriendshipEvent::EnergyRoot), -3);
+    }
+
+    #[test]
+    fn soothe_bell_only_boosts_the_gains() {
+        let mut pokemon = make_pokemon(1, 5);
+        pokemon.held_item = Some(SOOTHE_BELL.to_string());
+        pokemon.friendship = 70;
+        assert_eq!(pokemon.on_friendship_event(FriendshipEvent::Vitamin), 7);
+        assert_eq!(
+            pokemon.on_friendship_event(FriendshipEvent::BitterPowder),
+            -5
+        );
+    }
+
+    #[test]
+    fn return_and_frustration_powers() {
+        let mut pokemon = make_pokemon(1, 5);
+        pokemon.friendship = MAX_FRIENDSHIP;
+        assert_eq!(pokemon.get_return_power(), 102);
+        assert_eq!(pokemon.get_frustration_power(), 1);
+        pokemon.friendship = 0;
+        assert_eq!(pokemon.get_return_power(), 1);
+        assert_eq!(pokemon.get_frustration_power(), 102);
+    }
+
+    #[test]
+    fn leveling_up_raises_the_friendship() {
+        let mut pokemon = make_pokemon(1, 5);
+        pokemon.friendship = 70;
+        let experience = pokemon.get_experience_to_next_level();
+        assert_eq!(pokemon.gain_experience(experience), [6]);
+        assert_eq!(pokemon.friendship, 75);
+    }
+}
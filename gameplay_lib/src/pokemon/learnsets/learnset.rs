@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+
+use crate::pokemon::{
+    moves::{self, moveset::LearnMethod},
+    pokedex,
+};
+
+/// A level of 0 means the move is learned on evolution
+#[derive(Debug, Clone, Copy)]
+pub struct LevelUpMove {
+    pub name: moves::names::Name,
+    pub level: u8,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TechnicalMachineMove {
+    pub name: moves::names::Name,
+    pub id: u16,
+}
+
+/// Every move a species can learn and how
+#[derive(Debug)]
+pub struct Learnset {
+    pub species: pokedex::names::Name,
+    pub leveling_up: Vec<LevelUpMove>,
+    pub tm: Vec<TechnicalMachineMove>,
+    pub egg: Vec<moves::names::Name>,
+    pub tutor: Vec<moves::names::Name>,
+}
+
+impl Learnset {
+    fn get_species(toml: &toml::Table) -> Result<pokedex::names::Name, ErrorCode> {
+        let species = Toml::get_string(toml, "name")?;
+        pokedex::names::Name::from_str(&species)
+    }
+
+    fn get_move_name(
+        species: pokedex::names::Name,
+        list: &str,
+        name: &str,
+    ) -> Result<moves::names::Name, ErrorCode> {
+        match moves::names::Name::from_str(name) {
+            Ok(name) => Ok(name),
+            Err(err) => {
+                error!(
+                    "The move `{}' in the `{}' learnset of {:?} is not a valid move",
+                    name, list, species
+                );
+                Err(err)
+            }
+        }
+    }
+
+    fn get_leveling_up(
+        species: pokedex::names::Name,
+        toml: &toml::Table,
+    ) -> Result<Vec<LevelUpMove>, ErrorCode> {
+        let mut leveling_up: Vec<LevelUpMove> = Toml::get_table_list(toml, "leveling_up")?
+            .into_iter()
+            .map(|table| {
+                let name = Toml::get_string(table, "move")?;
+                let name = Self::get_move_name(species, "leveling_up", &name)?;
+                let level = Toml::get_u8(table, "level")?;
+                Ok(LevelUpMove { name, level })
+            })
+            .collect::<Result<_, ErrorCode>>()?;
+        leveling_up.sort_by_key(|level_up_move| level_up_move.level);
+        Ok(leveling_up)
+    }
+
+    fn get_tm(
+        species: pokedex::names::Name,
+        toml: &toml::Table,
+    ) -> Result<Vec<TechnicalMachineMove>, ErrorCode> {
+        Toml::get_table_list(toml, "tm")?
+            .into_iter()
+            .map(|table| {
+                let name = Toml::get_string(table, "move")?;
+                let name = Self::get_move_name(species, "tm", &name)?;
+                let id = Toml::get_u16(table, "id")?;
+                Ok(TechnicalMachineMove { name, id })
+            })
+            .collect()
+    }
+
+    fn get_move_list(
+        species: pokedex::names::Name,
+        toml: &toml::Table,
+        list: &str,
+    ) -> Result<Vec<moves::names::Name>, ErrorCode> {
+        Toml::get_string_list(toml, list)?
+            .iter()
+            .map(|name| Self::get_move_name(species, list, name))
+            .collect()
+    }
+
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let species = Self::get_species(toml)?;
+        let leveling_up = Self::get_leveling_up(species, toml)?;
+        let tm = Self::get_tm(species, toml)?;
+        let egg = Self::get_move_list(species, toml, "egg")?;
+        let tutor = Self::get_move_list(species, toml, "tutor")?;
+
+        Ok(Self {
+            species,
+            leveling_up,
+            tm,
+            egg,
+            tutor,
+        })
+    }
+
+    /// The moves learned when reaching exactly the given level
+    pub fn get_moves_learned_at_level(&self, level: u8) -> Vec<moves::names::Name> {
+        self.leveling_up
+            .iter()
+            .filter(|level_up_move| level_up_move.level == level)
+            .map(|level_up_move| level_up_move.name)
+            .collect()
+    }
+
+    /// The moves learned by leveling up until the given level, from the oldest to the newest
+    pub fn get_moves_learned_until_level(&self, level: u8) -> Vec<moves::names::Name> {
+        self.leveling_up
+            .iter()
+            .filter(|level_up_move| level_up_move.level <= level)
+            .map(|level_up_move| level_up_move.name)
+            .collect()
+    }
+
+    pub fn can_learn_by_leveling_up(&self, name: moves::names::Name) -> bool {
+        self.leveling_up
+            .iter()
+            .any(|level_up_move| level_up_move.name == name)
+    }
+
+    pub fn can_learn_by_tm(&self, name: moves::names::Name) -> bool {
+        self.tm.iter().any(|tm_move| tm_move.name == name)
+    }
+
+    pub fn can_learn_by(&self, name: moves::names::Name, method: LearnMethod) -> bool {
+        match method {
+            LearnMethod::LevelUp(level) => {
+                self.get_moves_learned_until_level(level).contains(&name)
+            }
+            LearnMethod::TechnicalMachine => self.can_learn_by_tm(name),
+            LearnMethod::Tutor => self.tutor.contains(&name),
+            LearnMethod::Egg => self.egg.contains(&name),
+        }
+    }
+
+    pub fn can_learn(&self, name: moves::names::Name) -> bool {
+        self.can_learn_by_leveling_up(name)
+            || self.can_learn_by_tm(name)
+            || self.egg.contains(&name)
+            || self.tutor.contains(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{moves::names::Name, pokedex};
+
+    use super::Learnset;
+
+    fn make_learnset(toml: &str) -> Result<Learnset, core_lib::utils::debug::ErrorCode> {
+        Learnset::from_toml(&toml.parse::<toml::Table>().unwrap())
+    }
+
+    const LEARNSET: &str = r#"
+        name = "Bulbasaur"
+        leveling_up = [
+            {move = "VineWhip", level = 3},
+            {move = "Tackle", level = 1},
+            {move = "Growl", level = 1},
+        ]
+        tm = [{move = "Protect", id = 7}]
+        egg = ["SkullBash"]
+        tutor = []
+    "#;
+
+    #[test]
+    fn level_up_moves_are_sorted_by_level() {
+        let learnset = make_learnset(LEARNSET).unwrap();
+        assert_eq!(learnset.species, pokedex::names::Name::Bulbasaur);
+        assert_eq!(
+            learnset.get_moves_learned_at_level(1),
+            [Name::Tackle, Name::Growl]
+        );
+        assert_eq!(learnset.get_moves_learned_at_level(2), []);
+        assert_eq!(
+            learnset.get_moves_learned_until_level(3),
+            [Name::Tackle, Name::Growl, Name::VineWhip]
+        );
+    }
+
+    #[test]
+    fn every_learn_method_is_checked() {
+        let learnset = make_learnset(LEARNSET).unwrap();
+        assert!(learnset.can_learn(Name::VineWhip));
+        assert!(learnset.can_learn(Name::Protect));
+        assert!(learnset.can_learn(Name::SkullBash));
+        assert!(!learnset.can_learn(Name::SolarBeam));
+    }
+
+    #[test]
+    fn unknown_moves_are_rejected() {
+        let toml = LEARNSET.replace("SkullBash", "Splash");
+        assert!(make_learnset(&toml).is_err());
+    }
+}
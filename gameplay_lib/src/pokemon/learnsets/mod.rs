@@ -0,0 +1,142 @@
+pub mod learnset;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use learnset::Learnset;
+use log::error;
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
+
+use super::{moves, pokedex};
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Learnsets {
+    pub data: HashMap<pokedex::names::Name, Learnset>,
+}
+
+impl Learnsets {
+    async fn get_toml() -> Result<toml::Table, ErrorCode> {
+        let mut move_sets_toml_path = PathBuf::from("");
+        move_sets_toml_path.push("assets");
+        move_sets_toml_path.push("data");
+        move_sets_toml_path.push("move_sets");
+        move_sets_toml_path.set_extension("toml");
+
+        Toml::get_toml(&move_sets_toml_path).await
+    }
+
+    fn new() -> Result<Self, ErrorCode> {
+        let toml = match pollster::block_on(Self::get_toml()) {
+            Ok(toml) => toml,
+            Err(err) => {
+                error!("Failed to block on the Learnsets' toml: {:?}", err);
+                return Err(ErrorCode::Unknown);
+            }
+        };
+        let mut data: HashMap<pokedex::names::Name, Learnset> = Default::default();
+
+        let movesets_array = match toml
+            .get("movesets")
+            .and_then(|movesets| movesets.as_array())
+        {
+            Some(movesets_array) => movesets_array,
+            None => {
+                error!("Failed to read the movesets from the Learnsets' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        let movesets_table = match movesets_array
+            .first()
+            .and_then(|movesets| movesets.as_table())
+        {
+            Some(movesets_table) => movesets_table,
+            None => {
+                error!("Failed to read the movesets table from the Learnsets' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        for (name, learnset_table) in movesets_table.iter() {
+            let learnset = match learnset_table.as_table().map(Learnset::from_toml) {
+                Some(Ok(learnset)) => learnset,
+                Some(Err(err)) => {
+                    error!("Failed to create the learnset of {}: {:?}", name, err);
+                    return Err(ErrorCode::Unknown);
+                }
+                None => {
+                    error!(
+                        "The learnset of {} is not a table in the Learnsets' toml",
+                        name
+                    );
+                    return Err(ErrorCode::BadValue);
+                }
+            };
+            if data.contains_key(&learnset.species) {
+                error!("The learnset of {:?} is defined twice", learnset.species);
+                return Err(ErrorCode::Duplicate);
+            }
+            data.insert(learnset.species, learnset);
+        }
+
+        Ok(Self { data })
+    }
+
+    pub fn get(&self, species: pokedex::names::Name) -> Result<&Learnset, ErrorCode> {
+        match self.data.get(&species) {
+            Some(learnset) => Ok(learnset),
+            None => {
+                error!("The species {:?} has no learnset", species);
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+
+    /// Tells if the given species can learn the given move by any means
+    pub fn can_species_learn(
+        &self,
+        species: pokedex::names::Name,
+        name: moves::names::Name,
+    ) -> bool {
+        self.data
+            .get(&species)
+            .is_some_and(|learnset| learnset.can_learn(name))
+    }
+}
+
+static GLOBAL_LEARNSETS: OnceLock<Arc<Learnsets>> = OnceLock::new();
+
+pub fn get_global_learnsets() -> Result<Arc<Learnsets>, ErrorCode> {
+    if let Some(learnsets) = GLOBAL_LEARNSETS.get() {
+        return Ok(learnsets.clone());
+    }
+    let learnsets = match Learnsets::new() {
+        Ok(learnsets) => learnsets,
+        Err(err) => {
+            error!("Failed to initialize the global learnsets: {:?}", err);
+            return Err(ErrorCode::Unknown);
+        }
+    };
+    // Keeps the first value if another thread initialized it in the meantime
+    Ok(GLOBAL_LEARNSETS.get_or_init(|| Arc::new(learnsets)).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::{moves, pokedex};
+
+    use super::get_global_learnsets;
+
+    #[test]
+    fn learnsets_are_read_from_the_toml() {
+        let learnsets = get_global_learnsets().unwrap();
+        let bulbasaur = learnsets.get(pokedex::names::Name::Bulbasaur).unwrap();
+        assert_eq!(bulbasaur.get_moves_learned_at_level(15).len(), 2);
+        assert!(learnsets.can_species_learn(
+            pokedex::names::Name::Bulbasaur,
+            moves::names::Name::Substitute
+        ));
+    }
+}
@@ -1,13 +1,34 @@
-use moves::Move;
-use pokedex::entry::PokedexEntry;
-use stats::Stats;
+use std::path::PathBuf;
+
+use friendship::FriendshipEvent;
+use moves::moveset::Moveset;
+use natures::Nature;
+use pokedex::{
+    breeding::Gender,
+    entry::PokedexEntry,
+    experience_group::{get_experience_from_group, get_level_from_experience, MAX_LEVEL},
+};
+use stats::{StatBreakdown, StatType, Stats};
 
 pub mod abilities;
+pub mod battle;
+pub mod capture;
+pub mod damage;
+pub mod daycare;
 pub mod egg;
 pub mod evolutions;
+pub mod factory;
+pub mod field;
+pub mod friendship;
+pub mod learnsets;
 pub mod moves;
+pub mod natures;
+pub mod personality;
 pub mod pokedex;
+pub mod rewards;
 pub mod stats;
+pub mod status;
+pub mod storage;
 pub mod types;
 
 pub type Id = usize;
@@ -18,17 +39,223 @@ pub struct Pokemon {
     pub nickname: String,
     pub id: Id,
 
+    pub gender: Gender,
     pub ability: abilities::names::Name,
-    pub is_shiny: bool,
+    /// Random value from which the gender, the nature, the ability and the shininess are derived
+    pub personality_value: u32,
+    pub original_trainer: personality::TrainerId,
+    pub held_item: Option<String>,
 
     pub friendship: u8,
+    pub level: u8,
+    /// Total amount of experience gathered since level 1
+    pub experience: u32,
+    pub nature: Nature,
 
-    pub move_1: Move,
-    pub move_2: Option<Move>,
-    pub move_3: Option<Move>,
-    pub move_4: Option<Move>,
+    pub moveset: Moveset,
 
     pub evs: Stats,
     pub ivs: Stats,
     pub stats: Stats,
+    pub current_hp: u16,
+    pub status: Option<status::Status>,
+}
+
+impl Pokemon {
+    pub fn get_sprites(&self) -> &Vec<PathBuf> {
+        if self.is_shiny() {
+            &self.pokedex_entry.shiny_sprites
+        } else {
+            &self.pokedex_entry.sprites
+        }
+    }
+
+    /// The nickname followed by the gender symbol
+    pub fn get_display_name(&self) -> String {
+        match self.gender {
+            Gender::Neutral => self.nickname.clone(),
+            gender => format!("{} {}", self.nickname, gender),
+        }
+    }
+
+    /// Restores the PP of the move in the given slot, like an Ether
+    /// Returns the PP actually restored
+    pub fn restore_move_power_points(&mut self, slot: usize, amount: u8) -> u8 {
+        self.moveset
+            .get_mut(slot)
+            .map_or(0, |known| known.restore_power_points(amount))
+    }
+
+    /// Restores the PP of every move, like an Elixir
+    /// Returns the PP actually restored in total
+    pub fn restore_moves_power_points(&mut self, amount: u8) -> u16 {
+        self.moveset
+            .iter_mut()
+            .map(|known| known.restore_power_points(amount) as u16)
+            .sum()
+    }
+
+    /// Restores the HP, the status and the PP, like a Pokemon Center
+    pub fn heal_fully(&mut self) {
+        self.current_hp = self.stats.hp;
+        self.status = None;
+        self.restore_moves_power_points(u8::MAX);
+    }
+
+    /// Details the computation of the given stat
+    pub fn get_stat_breakdown(&self, stat_type: StatType) -> StatBreakdown {
+        StatBreakdown::new(
+            stat_type,
+            self.pokedex_entry.base_stats.get(stat_type),
+            self.ivs.get(stat_type),
+            self.evs.get(stat_type),
+            self.level,
+            self.nature.get_stat_modifier(stat_type),
+        )
+    }
+
+    /// Recomputes the stats from the base stats, the IVs, the EVs, the level and the nature
+    /// The current HP change as much as the maximum HP
+    pub fn update_stats(&mut self) {
+        let old_max_hp = self.stats.hp;
+        for stat_type in StatType::ALL {
+            let value = self.get_stat_breakdown(stat_type).value;
+            self.stats.set(stat_type, value);
+        }
+        if old_max_hp == 0 {
+            self.current_hp = self.stats.hp;
+        } else if self.current_hp > 0 {
+            let new_hp = self.current_hp as i32 + self.stats.hp as i32 - old_max_hp as i32;
+            self.current_hp = new_hp.clamp(1, self.stats.hp as i32) as u16;
+        }
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.current_hp == 0
+    }
+
+    /// Sets the level and the matching minimum amount of experience
+    pub fn set_level(&mut self, level: u8) {
+        self.level = level.clamp(1, MAX_LEVEL);
+        self.experience =
+            get_experience_from_group(self.pokedex_entry.experience_group, self.level);
+        self.update_stats();
+    }
+
+    /// Amount of experience still needed to reach the next level, 0 at the maximum level
+    pub fn get_experience_to_next_level(&self) -> u32 {
+        if self.level >= MAX_LEVEL {
+            return 0;
+        }
+        let group = self.pokedex_entry.experience_group;
+        get_experience_from_group(group, self.level + 1).saturating_sub(self.experience)
+    }
+
+    /// Gives experience to the pokemon
+    /// Returns every level reached, in order
+    pub fn gain_experience(&mut self, amount: u32) -> Vec<u8> {
+        let group = self.pokedex_entry.experience_group;
+        let max_experience = get_experience_from_group(group, MAX_LEVEL);
+        self.experience = self.experience.saturating_add(amount).min(max_experience);
+
+        let old_level = self.level;
+        let new_level = get_level_from_experience(group, self.experience);
+        if new_level <= old_level {
+            return Vec::new();
+        }
+        self.level = new_level;
+        self.update_stats();
+        for _ in old_level..new_level {
+            self.on_friendship_event(FriendshipEvent::LevelUp);
+        }
+        (old_level + 1..=new_level).collect()
+    }
+
+    pub fn set_evs(&mut self, evs: Stats) {
+        self.evs = evs;
+        self.update_stats();
+    }
+
+    /// Adds EVs while respecting the per stat and total caps
+    /// Returns the EVs actually gained
+    pub fn gain_evs(&mut self, evs: &Stats) -> Stats {
+        let gained = rewards::get_capped_evs(&self.evs, evs);
+        for stat_type in StatType::ALL {
+            let value = self.evs.get(stat_type) + gained.get(stat_type);
+            self.evs.set(stat_type, value);
+        }
+        self.update_stats();
+        gained
+    }
+
+    /// Applies the rewards gained after defeating a pokemon
+    /// Returns every level reached, in order
+    pub fn apply_reward(&mut self, reward: &rewards::Reward) -> Vec<u8> {
+        self.gain_evs(&reward.evs);
+        self.gain_experience(reward.experience)
+    }
+
+    pub fn set_nature(&mut self, nature: Nature) {
+        self.nature = nature;
+        self.update_stats();
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use std::path::Path;
+
+    use core_lib::utils::toml::Toml;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{
+        abilities,
+        factory::PokemonFactory,
+        moves::entry::MoveEntry,
+        natures,
+        pokedex::{breeding::Gender, entry::PokedexEntry, Id},
+        stats::Stats,
+        types::Type,
+        Pokemon,
+    };
+
+    /// An entry of a data toml, with some of its values replaced
+    fn get_data_table(data: &str, key: &str, overrides: &str) -> toml::Table {
+        let path = Path::new("assets/data").join(data).with_extension("toml");
+        let toml = pollster::block_on(Toml::get_toml(&path)).unwrap();
+        let mut table = toml[data][0][key].as_table().unwrap().clone();
+        table.extend(toml::from_str::<toml::Table>(overrides).unwrap());
+        table
+    }
+
+    /// Bulbasaur's pokedex entry with other types, to test every matchup
+    pub(crate) fn make_pokedex_entry(types: &[Type]) -> &'static PokedexEntry {
+        let types: Vec<String> = types
+            .iter()
+            .map(|element_type| format!("{:?}", element_type))
+            .collect();
+        let overrides = format!("types = {:?}", types);
+        let table = get_data_table("pokedex", "bulbasaur", &overrides);
+        Box::leak(Box::new(PokedexEntry::from_toml(&table).unwrap()))
+    }
+
+    /// The entry of a move with some of its values replaced
+    pub(crate) fn make_move_entry(key: &str, overrides: &str) -> &'static MoveEntry {
+        let table = get_data_table("moves", key, overrides);
+        Box::leak(Box::new(MoveEntry::from_toml(&table).unwrap()))
+    }
+
+    /// A male pokemon of the given species and level, with a neutral nature and no IVs
+    pub(crate) fn make_pokemon(id: Id, level: u8) -> Pokemon {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pokemon = PokemonFactory::default()
+            .generate(id, level, &mut rng)
+            .unwrap();
+        pokemon.gender = Gender::Male;
+        pokemon.ability = abilities::names::Name::Overgrow;
+        pokemon.ivs = Stats::default();
+        let natures = natures::get_global_natures().unwrap();
+        pokemon.set_nature(natures.get(natures::names::Name::Hardy).unwrap());
+        pokemon
+    }
 }
@@ -0,0 +1,158 @@
+use std::str::FromStr;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+
+use crate::pokemon::{
+    battle::stages::BattleStat,
+    field::{Terrain, Weather},
+    status::Status,
+};
+
+/// The pokemon an effect applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectTarget {
+    User,
+    Target,
+}
+
+impl FromStr for EffectTarget {
+    type Err = ErrorCode;
+
+    fn from_str(target: &str) -> Result<Self, ErrorCode> {
+        match target {
+            "User" => Ok(EffectTarget::User),
+            "Target" => Ok(EffectTarget::Target),
+            _ => {
+                error!("The target {} is not a valid effect target", target);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// Behavior of a move on top of its damage, interpreted by the battle engine
+/// The chances and the fractions are given in percent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveEffect {
+    InflictStatus {
+        status: Status,
+        target: EffectTarget,
+        chance: u8,
+    },
+    Confuse {
+        target: EffectTarget,
+        chance: u8,
+    },
+    StatChange {
+        stat: BattleStat,
+        amount: i8,
+        target: EffectTarget,
+        chance: u8,
+    },
+    /// Heals the user by a fraction of the damage dealt
+    Drain(u8),
+    /// Hurts the user by a fraction of the damage dealt
+    Recoil(u8),
+    /// Hurts the user by a fraction of its maximum HP, whatever the damage dealt
+    MaxHpRecoil(u8),
+    /// Heals the user by a fraction of its maximum HP
+    Heal(u8),
+    /// Heals half of the maximum HP, more under the sun and less under any other weather
+    WeatherHeal,
+    /// The move is used on the turn after it is selected
+    /// Its stat changes on the user happen on the charge turn
+    ChargeTurn,
+    /// Charges except under the sun, the power is halved under any other weather
+    SolarCharge,
+    /// The user can't be hit while charging
+    SemiInvulnerableTurn,
+    Protect,
+    Substitute,
+    /// Leech Seed
+    Seed,
+    Rest,
+    SetWeather(Weather),
+    SetTerrain(Terrain),
+    /// Takes the type of the weather and doubles its power
+    WeatherType,
+    /// Raises the priority by one on the given terrain
+    TerrainPriority(Terrain),
+}
+
+impl MoveEffect {
+    /// Happens every time when not given
+    fn get_chance(toml: &toml::Table) -> Result<u8, ErrorCode> {
+        if toml.contains_key("chance") {
+            Toml::get_u8(toml, "chance")
+        } else {
+            Ok(100)
+        }
+    }
+
+    /// The target of the move when not given
+    fn get_target(toml: &toml::Table) -> Result<EffectTarget, ErrorCode> {
+        if toml.contains_key("target") {
+            let target = Toml::get_string(toml, "target")?;
+            EffectTarget::from_str(&target)
+        } else {
+            Ok(EffectTarget::Target)
+        }
+    }
+
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let effect_type = Toml::get_string(toml, "type")?;
+        match effect_type.as_str() {
+            "InflictStatus" => {
+                let status = Toml::get_string(toml, "status")?;
+                Ok(MoveEffect::InflictStatus {
+                    status: Status::from_str(&status)?,
+                    target: Self::get_target(toml)?,
+                    chance: Self::get_chance(toml)?,
+                })
+            }
+            "Confuse" => Ok(MoveEffect::Confuse {
+                target: Self::get_target(toml)?,
+                chance: Self::get_chance(toml)?,
+            }),
+            "StatChange" => {
+                let stat = Toml::get_string(toml, "stat")?;
+                Ok(MoveEffect::StatChange {
+                    stat: BattleStat::from_str(&stat)?,
+                    amount: Toml::get_i8(toml, "amount")?,
+                    target: Self::get_target(toml)?,
+                    chance: Self::get_chance(toml)?,
+                })
+            }
+            "Drain" => Ok(MoveEffect::Drain(Toml::get_u8(toml, "percent")?)),
+            "Recoil" => Ok(MoveEffect::Recoil(Toml::get_u8(toml, "percent")?)),
+            "MaxHpRecoil" => Ok(MoveEffect::MaxHpRecoil(Toml::get_u8(toml, "percent")?)),
+            "Heal" => Ok(MoveEffect::Heal(Toml::get_u8(toml, "percent")?)),
+            "WeatherHeal" => Ok(MoveEffect::WeatherHeal),
+            "ChargeTurn" => Ok(MoveEffect::ChargeTurn),
+            "SolarCharge" => Ok(MoveEffect::SolarCharge),
+            "SemiInvulnerableTurn" => Ok(MoveEffect::SemiInvulnerableTurn),
+            "Protect" => Ok(MoveEffect::Protect),
+            "Substitute" => Ok(MoveEffect::Substitute),
+            "Seed" => Ok(MoveEffect::Seed),
+            "Rest" => Ok(MoveEffect::Rest),
+            "SetWeather" => {
+                let weather = Toml::get_string(toml, "weather")?;
+                Ok(MoveEffect::SetWeather(Weather::from_str(&weather)?))
+            }
+            "SetTerrain" => {
+                let terrain = Toml::get_string(toml, "terrain")?;
+                Ok(MoveEffect::SetTerrain(Terrain::from_str(&terrain)?))
+            }
+            "WeatherType" => Ok(MoveEffect::WeatherType),
+            "TerrainPriority" => {
+                let terrain = Toml::get_string(toml, "terrain")?;
+                Ok(MoveEffect::TerrainPriority(Terrain::from_str(&terrain)?))
+            }
+            _ => {
+                error!("The type {} is not a valid move effect", effect_type);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
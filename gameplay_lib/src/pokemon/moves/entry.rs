@@ -0,0 +1,212 @@
+use std::str::FromStr;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+
+use crate::pokemon::types::Type;
+
+use super::{effects::MoveEffect, names::Name, MoveType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveTarget {
+    User,
+    SelectedOpponent,
+    AllOpponents,
+    AllOthers,
+    Ally,
+    UserAndAllies,
+    RandomOpponent,
+    EntireField,
+}
+
+impl MoveTarget {
+    pub fn targets_opponent(&self) -> bool {
+        matches!(
+            self,
+            MoveTarget::SelectedOpponent
+                | MoveTarget::AllOpponents
+                | MoveTarget::AllOthers
+                | MoveTarget::RandomOpponent
+        )
+    }
+}
+
+impl FromStr for MoveTarget {
+    type Err = ErrorCode;
+
+    fn from_str(target: &str) -> Result<Self, ErrorCode> {
+        match target {
+            "User" => Ok(MoveTarget::User),
+            "SelectedOpponent" => Ok(MoveTarget::SelectedOpponent),
+            "AllOpponents" => Ok(MoveTarget::AllOpponents),
+            "AllOthers" => Ok(MoveTarget::AllOthers),
+            "Ally" => Ok(MoveTarget::Ally),
+            "UserAndAllies" => Ok(MoveTarget::UserAndAllies),
+            "RandomOpponent" => Ok(MoveTarget::RandomOpponent),
+            "EntireField" => Ok(MoveTarget::EntireField),
+            _ => {
+                error!("The target {} is not a valid move target", target);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveFlag {
+    /// Makes contact with the target
+    Contact,
+    /// Blocked by Protect and similar moves
+    Protect,
+    /// Bounced back by Magic Coat
+    Reflectable,
+    /// Stolen by Snatch
+    Snatch,
+    /// Copied by Mirror Move
+    Mirror,
+    Sound,
+    Powder,
+    Ballistic,
+    Heal,
+    /// Needs a turn of charge before being used
+    Charge,
+}
+
+impl FromStr for MoveFlag {
+    type Err = ErrorCode;
+
+    fn from_str(flag: &str) -> Result<Self, ErrorCode> {
+        match flag {
+            "Contact" => Ok(MoveFlag::Contact),
+            "Protect" => Ok(MoveFlag::Protect),
+            "Reflectable" => Ok(MoveFlag::Reflectable),
+            "Snatch" => Ok(MoveFlag::Snatch),
+            "Mirror" => Ok(MoveFlag::Mirror),
+            "Sound" => Ok(MoveFlag::Sound),
+            "Powder" => Ok(MoveFlag::Powder),
+            "Ballistic" => Ok(MoveFlag::Ballistic),
+            "Heal" => Ok(MoveFlag::Heal),
+            "Charge" => Ok(MoveFlag::Charge),
+            _ => {
+                error!("The flag {} is not a valid move flag", flag);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// The static definition of a move
+#[derive(Debug)]
+pub struct MoveEntry {
+    pub name: Name,
+    pub element_type: Type,
+    pub category: MoveType,
+
+    /// None for moves without a fixed power
+    pub power: Option<u8>,
+    /// None for moves which never miss
+    pub accuracy: Option<u8>,
+    pub power_points: u8,
+    pub priority: i8,
+
+    pub target: MoveTarget,
+    pub flags: Vec<MoveFlag>,
+    pub effects: Vec<MoveEffect>,
+}
+
+impl MoveEntry {
+    pub fn has_flag(&self, flag: MoveFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    pub fn has_effect(&self, effect: MoveEffect) -> bool {
+        self.effects.contains(&effect)
+    }
+
+    fn get_name(toml: &toml::Table) -> Result<Name, ErrorCode> {
+        let name = Toml::get_string(toml, "name")?;
+        Name::from_str(&name)
+    }
+
+    fn get_element_type(toml: &toml::Table) -> Result<Type, ErrorCode> {
+        let element_type = Toml::get_string(toml, "type")?;
+        Type::from_str(&element_type)
+    }
+
+    fn get_category(toml: &toml::Table) -> Result<MoveType, ErrorCode> {
+        let category = Toml::get_string(toml, "category")?;
+        MoveType::from_str(&category)
+    }
+
+    fn get_power(toml: &toml::Table) -> Result<Option<u8>, ErrorCode> {
+        let power = Toml::get_u8(toml, "power")?;
+        if power == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(power))
+        }
+    }
+
+    fn get_accuracy(toml: &toml::Table) -> Result<Option<u8>, ErrorCode> {
+        let accuracy = Toml::get_u8(toml, "accuracy")?;
+        if accuracy == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(accuracy))
+        }
+    }
+
+    fn get_power_points(toml: &toml::Table) -> Result<u8, ErrorCode> {
+        Toml::get_u8(toml, "power_points")
+    }
+
+    fn get_priority(toml: &toml::Table) -> Result<i8, ErrorCode> {
+        Toml::get_i8(toml, "priority")
+    }
+
+    fn get_target(toml: &toml::Table) -> Result<MoveTarget, ErrorCode> {
+        let target = Toml::get_string(toml, "target")?;
+        MoveTarget::from_str(&target)
+    }
+
+    fn get_flags(toml: &toml::Table) -> Result<Vec<MoveFlag>, ErrorCode> {
+        let flag_names = Toml::get_string_list(toml, "flags")?;
+        flag_names
+            .iter()
+            .map(|flag| MoveFlag::from_str(flag))
+            .collect()
+    }
+
+    fn get_effects(toml: &toml::Table) -> Result<Vec<MoveEffect>, ErrorCode> {
+        Toml::get_table_list(toml, "effects")?
+            .into_iter()
+            .map(MoveEffect::from_toml)
+            .collect()
+    }
+
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let name = Self::get_name(toml)?;
+        let element_type = Self::get_element_type(toml)?;
+        let category = Self::get_category(toml)?;
+        let power = Self::get_power(toml)?;
+        let accuracy = Self::get_accuracy(toml)?;
+        let power_points = Self::get_power_points(toml)?;
+        let priority = Self::get_priority(toml)?;
+        let target = Self::get_target(toml)?;
+        let flags = Self::get_flags(toml)?;
+        let effects = Self::get_effects(toml)?;
+
+        Ok(Self {
+            name,
+            element_type,
+            category,
+            power,
+            accuracy,
+            power_points,
+            priority,
+            target,
+            flags,
+            effects,
+        })
+    }
+}
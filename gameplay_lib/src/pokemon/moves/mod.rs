@@ -1,19 +1,363 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use entry::MoveEntry;
+use log::error;
 use names::Name;
 
+pub mod effects;
+pub mod entry;
+pub mod moveset;
 pub mod names;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MoveType {
     Special,
     Physique,
     Status,
 }
 
+impl FromStr for MoveType {
+    type Err = ErrorCode;
+
+    fn from_str(move_type: &str) -> Result<Self, ErrorCode> {
+        match move_type {
+            "Special" => Ok(MoveType::Special),
+            "Physique" => Ok(MoveType::Physique),
+            "Status" => Ok(MoveType::Status),
+            _ => {
+                error!("The type {} is not a valid move type", move_type);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// Maximum number of moves a pokemon can know at once
+pub const MAX_NB_MOVES: usize = 4;
+
+/// Keeps the most recent distinct moves a pokemon could know from a list of learned moves
+pub fn keep_most_recent_moves(names: impl IntoIterator<Item = Name>) -> Vec<Name> {
+    let mut recent_names: Vec<Name> = Vec::new();
+    for name in names {
+        if let Some(index) = recent_names.iter().position(|known| *known == name) {
+            recent_names.remove(index);
+        }
+        recent_names.push(name);
+    }
+    let nb_names = recent_names.len();
+    recent_names.split_off(nb_names.saturating_sub(MAX_NB_MOVES))
+}
+
+/// Number of PP Up a move can take, a PP Max gives all of them at once
+pub const MAX_PP_UPS: u8 = 3;
+
+/// Maximum PP of a move, every PP Up adds a fifth of its base PP
+pub fn get_max_power_points(base_power_points: u8, nb_pp_ups: u8) -> u8 {
+    let base_power_points = base_power_points as u16;
+    let bonus = base_power_points * nb_pp_ups.min(MAX_PP_UPS) as u16 / 5;
+    (base_power_points + bonus).min(u8::MAX as u16) as u8
+}
+
+/// A move known by a pokemon
 pub struct Move {
-    pub name: Name,
-    pub move_type: MoveType,
+    pub entry: &'static MoveEntry,
+
+    pub current_power_points: u8,
+    pub max_power_points: u8,
+    pub nb_pp_ups: u8,
+}
+
+impl Move {
+    pub fn new(name: Name) -> Result<Self, ErrorCode> {
+        let entry = get_global_move_entry(name)?;
+        Ok(Self {
+            entry,
+            current_power_points: entry.power_points,
+            max_power_points: entry.power_points,
+            nb_pp_ups: 0,
+        })
+    }
+
+    /// Reads a move from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let name = Name::from_str(&Toml::get_string(toml, "name")?)?;
+        let mut known = Self::new(name)?;
+        known.set_nb_pp_ups(Toml::get_u8(toml, "nb_pp_ups")?.min(MAX_PP_UPS));
+        known.current_power_points =
+            Toml::get_u8(toml, "current_power_points")?.min(known.max_power_points);
+        Ok(known)
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        toml.insert("name".to_string(), format!("{:?}", self.get_name()).into());
+        toml.insert(
+            "current_power_points".to_string(),
+            self.current_power_points.into(),
+        );
+        toml.insert("nb_pp_ups".to_string(), self.nb_pp_ups.into());
+        toml
+    }
+
+    pub fn get_name(&self) -> Name {
+        self.entry.name
+    }
+
+    pub fn has_power_points(&self) -> bool {
+        self.current_power_points > 0
+    }
+
+    /// Uses PP without going under 0
+    /// Returns the PP actually used
+    pub fn use_power_points(&mut self, amount: u8) -> u8 {
+        let amount = amount.min(self.current_power_points);
+        self.current_power_points -= amount;
+        amount
+    }
+
+    /// Restores PP without going over the maximum
+    /// Returns the PP actually restored
+    pub fn restore_power_points(&mut self, amount: u8) -> u8 {
+        let amount = amount.min(self.max_power_points - self.current_power_points);
+        self.current_power_points += amount;
+        amount
+    }
+
+    pub fn restore_all_power_points(&mut self) -> u8 {
+        self.restore_power_points(u8::MAX)
+    }
+
+    /// The PP gained by raising the maximum are also restored
+    fn set_nb_pp_ups(&mut self, nb_pp_ups: u8) {
+        let max_power_points = get_max_power_points(self.entry.power_points, nb_pp_ups);
+        let gained = max_power_points.saturating_sub(self.max_power_points);
+        self.nb_pp_ups = nb_pp_ups;
+        self.max_power_points = max_power_points;
+        self.current_power_points = (self.current_power_points + gained).min(max_power_points);
+    }
+
+    /// Fails if the move already took every PP Up
+    pub fn apply_pp_up(&mut self) -> bool {
+        if self.nb_pp_ups >= MAX_PP_UPS {
+            return false;
+        }
+        self.set_nb_pp_ups(self.nb_pp_ups + 1);
+        true
+    }
+
+    /// Fails if the move already took every PP Up
+    pub fn apply_pp_max(&mut self) -> bool {
+        if self.nb_pp_ups >= MAX_PP_UPS {
+            return false;
+        }
+        self.set_nb_pp_ups(MAX_PP_UPS);
+        true
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Moves {
+    pub data: HashMap<Name, MoveEntry>,
+}
+
+impl Moves {
+    async fn get_toml() -> Result<toml::Table, ErrorCode> {
+        let mut moves_toml_path = PathBuf::from("");
+        moves_toml_path.push("assets");
+        moves_toml_path.push("data");
+        moves_toml_path.push("moves");
+        moves_toml_path.set_extension("toml");
+
+        Toml::get_toml(&moves_toml_path).await
+    }
+
+    fn new() -> Result<Self, ErrorCode> {
+        let toml = match pollster::block_on(Self::get_toml()) {
+            Ok(toml) => toml,
+            Err(err) => {
+                error!("Failed to block on the Moves' toml: {:?}", err);
+                return Err(ErrorCode::Unknown);
+            }
+        };
+        let mut data: HashMap<Name, MoveEntry> = Default::default();
+
+        let moves_array = match toml.get("moves").and_then(|moves| moves.as_array()) {
+            Some(moves_array) => moves_array,
+            None => {
+                error!("Failed to read the moves from the Moves' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        let moves_table = match moves_array.first().and_then(|moves| moves.as_table()) {
+            Some(moves_table) => moves_table,
+            None => {
+                error!("Failed to read the moves table from the Moves' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        for (name, move_table) in moves_table.iter() {
+            let move_entry = match move_table.as_table().map(MoveEntry::from_toml) {
+                Some(Ok(move_entry)) => move_entry,
+                Some(Err(err)) => {
+                    error!("Failed to create the move entry {}: {:?}", name, err);
+                    return Err(ErrorCode::Unknown);
+                }
+                None => {
+                    error!("The move {} is not a table in the Moves' toml", name);
+                    return Err(ErrorCode::BadValue);
+                }
+            };
+            if data.contains_key(&move_entry.name) {
+                error!("The move {:?} is defined twice", move_entry.name);
+                return Err(ErrorCode::Duplicate);
+            }
+            data.insert(move_entry.name, move_entry);
+        }
+
+        Ok(Self { data })
+    }
+}
+
+static GLOBAL_MOVES: OnceLock<Arc<Moves>> = OnceLock::new();
+
+fn get_static_moves() -> Result<&'static Arc<Moves>, ErrorCode> {
+    if let Some(moves) = GLOBAL_MOVES.get() {
+        return Ok(moves);
+    }
+    let moves = match Moves::new() {
+        Ok(moves) => moves,
+        Err(err) => {
+            error!("Failed to initialize the global moves: {:?}", err);
+            return Err(ErrorCode::Unknown);
+        }
+    };
+    // Keeps the first value if another thread initialized it in the meantime
+    Ok(GLOBAL_MOVES.get_or_init(|| Arc::new(moves)))
+}
+
+pub fn get_global_moves() -> Result<Arc<Moves>, ErrorCode> {
+    get_static_moves().cloned()
+}
+
+pub fn get_global_move_entry(name: Name) -> Result<&'static MoveEntry, ErrorCode> {
+    match get_static_moves()?.data.get(&name) {
+        Some(entry) => Ok(entry),
+        None => {
+            error!("The move {:?} is not in the global moves", name);
+            Err(ErrorCode::NotFound)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::types::Type;
+
+    use super::{
+        entry::MoveFlag, get_global_move_entry, get_max_power_points, keep_most_recent_moves,
+        names::Name, Move, MoveType, MAX_PP_UPS,
+    };
+
+    #[test]
+    fn moves_are_read_from_the_toml() {
+        let tackle = get_global_move_entry(Name::Tackle).unwrap();
+        assert_eq!(tackle.element_type, Type::Normal);
+        assert_eq!(tackle.category, MoveType::Physique);
+        assert_eq!(tackle.power, Some(40));
+        assert_eq!(tackle.accuracy, Some(100));
+        assert_eq!(tackle.power_points, 35);
+        assert!(tackle.has_flag(MoveFlag::Contact));
+    }
+
+    #[test]
+    fn a_zero_power_or_accuracy_means_none() {
+        let protect = get_global_move_entry(Name::Protect).unwrap();
+        assert_eq!(protect.power, None);
+        assert_eq!(protect.accuracy, None);
+        assert_eq!(protect.priority, 4);
+    }
+
+    #[test]
+    fn new_moves_have_full_power_points() {
+        let vine_whip = Move::new(Name::VineWhip).unwrap();
+        assert_eq!(vine_whip.get_name(), Name::VineWhip);
+        assert_eq!(vine_whip.current_power_points, vine_whip.entry.power_points);
+        assert_eq!(vine_whip.max_power_points, vine_whip.entry.power_points);
+    }
+
+    #[test]
+    fn only_the_four_most_recent_moves_are_kept() {
+        let names = [
+            Name::Tackle,
+            Name::Growl,
+            Name::VineWhip,
+            Name::Tackle,
+            Name::Growth,
+            Name::LeechSeed,
+        ];
+        assert_eq!(
+            keep_most_recent_moves(names),
+            [Name::VineWhip, Name::Tackle, Name::Growth, Name::LeechSeed]
+        );
+    }
+
+    #[test]
+    fn pp_ups_add_a_fifth_of_the_base_pp() {
+        assert_eq!(get_max_power_points(35, 0), 35);
+        assert_eq!(get_max_power_points(35, 1), 42);
+        assert_eq!(get_max_power_points(35, 3), 56);
+        assert_eq!(get_max_power_points(5, 3), 8);
+        assert_eq!(get_max_power_points(35, 10), 56);
+    }
+
+    #[test]
+    fn pp_up_raises_and_restores_the_maximum() {
+        let mut tackle = Move::new(Name::Tackle).unwrap();
+        tackle.use_power_points(10);
+        assert!(tackle.apply_pp_up());
+        assert_eq!(tackle.nb_pp_ups, 1);
+        assert_eq!(tackle.max_power_points, 42);
+        assert_eq!(tackle.current_power_points, 32);
+
+        assert!(tackle.apply_pp_max());
+        assert_eq!(tackle.nb_pp_ups, MAX_PP_UPS);
+        assert_eq!(tackle.max_power_points, 56);
+        assert_eq!(tackle.current_power_points, 46);
+        assert!(!tackle.apply_pp_up());
+        assert!(!tackle.apply_pp_max());
+        assert_eq!(tackle.max_power_points, 56);
+    }
+
+    #[test]
+    fn setting_the_pp_ups_keeps_the_current_pp_under_the_maximum() {
+        let mut tackle = Move::new(Name::Tackle).unwrap();
+        tackle.set_nb_pp_ups(3);
+        assert_eq!(tackle.max_power_points, 56);
+        assert_eq!(tackle.current_power_points, 56);
+
+        tackle.set_nb_pp_ups(1);
+        assert_eq!(tackle.max_power_points, 42);
+        assert_eq!(tackle.current_power_points, 42);
+    }
 
-    pub power_points: u8,
+    #[test]
+    fn power_points_stay_between_zero_and_the_maximum() {
+        let mut tackle = Move::new(Name::Tackle).unwrap();
+        assert_eq!(tackle.use_power_points(30), 30);
+        assert_eq!(tackle.use_power_points(10), 5);
+        assert!(!tackle.has_power_points());
 
-    pub power: Option<u8>,
-    pub precision: Option<u8>,
+        assert_eq!(tackle.restore_power_points(10), 10);
+        assert_eq!(tackle.restore_power_points(100), 25);
+        assert_eq!(tackle.current_power_points, 35);
+        tackle.use_power_points(1);
+        assert_eq!(tackle.restore_all_power_points(), 1);
+    }
 }
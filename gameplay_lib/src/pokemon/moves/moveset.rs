@@ -0,0 +1,397 @@
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+use crate::pokemon::{
+    learnsets::{get_global_learnsets, learnset::Learnset},
+    Pokemon,
+};
+
+use super::{names::Name, Move, MAX_NB_MOVES};
+
+/// How a pokemon is taught a move, each way is checked against a different part of the learnset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearnMethod {
+    /// By leveling up until the given level, or through the move reminder
+    LevelUp(u8),
+    TechnicalMachine,
+    Tutor,
+    Egg,
+}
+
+/// Outcome of teaching a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearnOutcome {
+    /// The move was put in the given slot
+    Learned(usize),
+    /// Every slot is taken, the UI has to ask which move to forget then call `replace`
+    MustForget,
+    AlreadyKnown,
+}
+
+/// The moves known by a pokemon, between 1 and `MAX_NB_MOVES` distinct moves
+pub struct Moveset {
+    moves: Vec<Move>,
+}
+
+impl Moveset {
+    pub fn new(names: &[Name]) -> Result<Self, ErrorCode> {
+        if names.is_empty() || names.len() > MAX_NB_MOVES {
+            error!(
+                "A pokemon can't know {} moves, it must know between 1 and {} moves",
+                names.len(),
+                MAX_NB_MOVES
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                error!("A pokemon can't know the move {:?} twice", name);
+                return Err(ErrorCode::Duplicate);
+            }
+        }
+        let moves = names
+            .iter()
+            .map(|name| Move::new(*name))
+            .collect::<Result<Vec<Move>, ErrorCode>>()?;
+        Ok(Self { moves })
+    }
+
+    /// Reads the moves and their PP from a save file
+    pub fn from_toml(toml: &[&toml::Table]) -> Result<Self, ErrorCode> {
+        let moves = toml
+            .iter()
+            .map(|move_toml| Move::from_toml(move_toml))
+            .collect::<Result<Vec<Move>, ErrorCode>>()?;
+        // Checks the number of moves and the duplicates
+        Self::new(&moves.iter().map(Move::get_name).collect::<Vec<Name>>())?;
+        Ok(Self { moves })
+    }
+
+    pub fn to_toml(&self) -> Vec<toml::Value> {
+        self.moves
+            .iter()
+            .map(|known| known.to_toml().into())
+            .collect()
+    }
+
+    /// The move in the given slot, starting at 0
+    pub fn get(&self, slot: usize) -> Option<&Move> {
+        self.moves.get(slot)
+    }
+
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut Move> {
+        self.moves.get_mut(slot)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Move> {
+        self.moves.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Move> {
+        self.moves.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Always false, a pokemon knows at least one move
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.moves.len() >= MAX_NB_MOVES
+    }
+
+    pub fn get_names(&self) -> Vec<Name> {
+        self.moves.iter().map(Move::get_name).collect()
+    }
+
+    pub fn contains(&self, name: Name) -> bool {
+        self.moves.iter().any(|known| known.get_name() == name)
+    }
+
+    fn check_slot(&self, slot: usize) -> Result<(), ErrorCode> {
+        if slot >= self.moves.len() {
+            error!("There is no move in the slot {}", slot);
+            return Err(ErrorCode::NotFound);
+        }
+        Ok(())
+    }
+
+    fn check_learnable(
+        name: Name,
+        method: LearnMethod,
+        learnset: &Learnset,
+    ) -> Result<(), ErrorCode> {
+        if !learnset.can_learn_by(name, method) {
+            error!(
+                "The species {:?} can't learn the move {:?} with {:?}",
+                learnset.species, name, method
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(())
+    }
+
+    /// Teaches the move in the first free slot
+    pub fn learn(
+        &mut self,
+        name: Name,
+        method: LearnMethod,
+        learnset: &Learnset,
+    ) -> Result<LearnOutcome, ErrorCode> {
+        Self::check_learnable(name, method, learnset)?;
+        if self.contains(name) {
+            return Ok(LearnOutcome::AlreadyKnown);
+        }
+        if self.is_full() {
+            return Ok(LearnOutcome::MustForget);
+        }
+        self.moves.push(Move::new(name)?);
+        Ok(LearnOutcome::Learned(self.moves.len() - 1))
+    }
+
+    /// Forgets the move in the given slot to teach the new one in its place
+    /// Returns the forgotten move
+    pub fn replace(
+        &mut self,
+        slot: usize,
+        name: Name,
+        method: LearnMethod,
+        learnset: &Learnset,
+    ) -> Result<Name, ErrorCode> {
+        self.check_slot(slot)?;
+        Self::check_learnable(name, method, learnset)?;
+        if self.contains(name) {
+            error!("The move {:?} is already known", name);
+            return Err(ErrorCode::Duplicate);
+        }
+        let forgotten = std::mem::replace(&mut self.moves[slot], Move::new(name)?);
+        Ok(forgotten.get_name())
+    }
+
+    /// Fails on the last known move
+    /// The following moves go up by one slot
+    pub fn forget(&mut self, slot: usize) -> Result<Name, ErrorCode> {
+        self.check_slot(slot)?;
+        if self.moves.len() == 1 {
+            error!("A pokemon can't forget its last move");
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(self.moves.remove(slot).get_name())
+    }
+
+    /// Swaps the order of two moves, their PP go with them
+    pub fn swap(&mut self, slot_1: usize, slot_2: usize) -> Result<(), ErrorCode> {
+        self.check_slot(slot_1)?;
+        self.check_slot(slot_2)?;
+        self.moves.swap(slot_1, slot_2);
+        Ok(())
+    }
+}
+
+impl Pokemon {
+    /// A pokemon can't learn by leveling up the moves of the levels it hasn't reached yet
+    fn get_reachable_method(&self, method: LearnMethod) -> LearnMethod {
+        match method {
+            LearnMethod::LevelUp(level) => LearnMethod::LevelUp(level.min(self.level)),
+            _ => method,
+        }
+    }
+
+    /// Teaches a move after checking the learnset of the species
+    pub fn learn_move(
+        &mut self,
+        name: Name,
+        method: LearnMethod,
+    ) -> Result<LearnOutcome, ErrorCode> {
+        let learnsets = get_global_learnsets()?;
+        let method = self.get_reachable_method(method);
+        self.moveset
+            .learn(name, method, learnsets.get(self.pokedex_entry.name)?)
+    }
+
+    /// Answers a `LearnOutcome::MustForget` by forgetting the move in the given slot
+    pub fn replace_move(
+        &mut self,
+        slot: usize,
+        name: Name,
+        method: LearnMethod,
+    ) -> Result<Name, ErrorCode> {
+        let learnsets = get_global_learnsets()?;
+        let method = self.get_reachable_method(method);
+        self.moveset
+            .replace(slot, name, method, learnsets.get(self.pokedex_entry.name)?)
+    }
+
+    /// The moves the move reminder can teach, the ones learned by leveling up but not known anymore
+    pub fn get_relearnable_moves(&self) -> Result<Vec<Name>, ErrorCode> {
+        let learnsets = get_global_learnsets()?;
+        let learnset = learnsets.get(self.pokedex_entry.name)?;
+        let mut names = Vec::new();
+        for name in learnset.get_moves_learned_until_level(self.level) {
+            if !self.moveset.contains(name) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Teaches a move again through the move reminder
+    pub fn relearn_move(&mut self, name: Name) -> Result<LearnOutcome, ErrorCode> {
+        self.learn_move(name, LearnMethod::LevelUp(self.level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core_lib::utils::debug::ErrorCode;
+
+    use crate::pokemon::test_utils::make_pokemon;
+
+    use super::{LearnMethod, LearnOutcome, Moveset, Name};
+
+    #[test]
+    fn movesets_hold_one_to_four_distinct_moves() {
+        assert!(Moveset::new(&[]).is_err());
+        assert_eq!(
+            Moveset::new(&[Name::Tackle, Name::Growl, Name::Tackle]).err(),
+            Some(ErrorCode::Duplicate)
+        );
+        let names = [
+            Name::Tackle,
+            Name::Growl,
+            Name::VineWhip,
+            Name::Growth,
+            Name::LeechSeed,
+        ];
+        assert!(Moveset::new(&names).is_err());
+        let moveset = Moveset::new(&names[..4]).unwrap();
+        assert!(moveset.is_full());
+        assert_eq!(moveset.get_names(), names[..4]);
+    }
+
+    #[test]
+    fn saved_movesets_are_validated() {
+        let mut moveset = Moveset::new(&[Name::Tackle, Name::Growl]).unwrap();
+        moveset.get_mut(0).unwrap().apply_pp_up();
+        moveset.get_mut(0).unwrap().use_power_points(3);
+        let toml: Vec<toml::Table> = moveset
+            .to_toml()
+            .into_iter()
+            .map(|value| value.as_table().unwrap().clone())
+            .collect();
+        let loaded = Moveset::from_toml(&toml.iter().collect::<Vec<_>>()).unwrap();
+        assert_eq!(loaded.get_names(), [Name::Tackle, Name::Growl]);
+        let tackle = loaded.get(0).unwrap();
+        assert_eq!((tackle.nb_pp_ups, tackle.current_power_points), (1, 39));
+
+        let duplicates = [&toml[0], &toml[1], &toml[0]];
+        assert_eq!(
+            Moveset::from_toml(&duplicates).err(),
+            Some(ErrorCode::Duplicate)
+        );
+        assert!(Moveset::from_toml(&[]).is_err());
+    }
+
+    #[test]
+    fn learning_fills_the_free_slots_then_asks_to_forget() {
+        let mut pokemon = make_pokemon(1, 5);
+        assert_eq!(
+            pokemon.moveset.get_names(),
+            [Name::Tackle, Name::Growl, Name::VineWhip]
+        );
+        assert_eq!(
+            pokemon.learn_move(Name::Protect, LearnMethod::TechnicalMachine),
+            Ok(LearnOutcome::Learned(3))
+        );
+        assert_eq!(
+            pokemon.learn_move(Name::Tackle, LearnMethod::LevelUp(1)),
+            Ok(LearnOutcome::AlreadyKnown)
+        );
+        assert_eq!(
+            pokemon.learn_move(Name::Charm, LearnMethod::TechnicalMachine),
+            Ok(LearnOutcome::MustForget)
+        );
+        assert_eq!(
+            pokemon.replace_move(0, Name::Charm, LearnMethod::TechnicalMachine),
+            Ok(Name::Tackle)
+        );
+        assert_eq!(pokemon.moveset.get(0).unwrap().get_name(), Name::Charm);
+        assert!(pokemon
+            .replace_move(1, Name::Charm, LearnMethod::TechnicalMachine)
+            .is_err());
+    }
+
+    #[test]
+    fn moves_must_be_in_the_learnset_for_the_method() {
+        let mut pokemon = make_pokemon(1, 5);
+        pokemon.moveset = Moveset::new(&[Name::Tackle]).unwrap();
+        assert!(pokemon
+            .learn_move(Name::SkullBash, LearnMethod::TechnicalMachine)
+            .is_err());
+        assert!(pokemon.learn_move(Name::Growl, LearnMethod::Tutor).is_err());
+        assert_eq!(
+            pokemon.learn_move(Name::SkullBash, LearnMethod::Egg),
+            Ok(LearnOutcome::Learned(1))
+        );
+    }
+
+    #[test]
+    fn level_up_moves_need_the_level_of_the_pokemon() {
+        // Growth is learned at level 6
+        let mut pokemon = make_pokemon(1, 5);
+        pokemon.moveset = Moveset::new(&[Name::Tackle]).unwrap();
+        assert!(pokemon
+            .learn_move(Name::Growth, LearnMethod::LevelUp(100))
+            .is_err());
+        assert!(pokemon
+            .replace_move(0, Name::Growth, LearnMethod::LevelUp(100))
+            .is_err());
+
+        pokemon.level = 6;
+        assert_eq!(
+            pokemon.learn_move(Name::Growth, LearnMethod::LevelUp(100)),
+            Ok(LearnOutcome::Learned(1))
+        );
+    }
+
+    #[test]
+    fn the_last_move_cant_be_forgotten() {
+        let mut moveset = Moveset::new(&[Name::Tackle, Name::Growl, Name::VineWhip]).unwrap();
+        assert_eq!(moveset.forget(0), Ok(Name::Tackle));
+        assert_eq!(moveset.get_names(), [Name::Growl, Name::VineWhip]);
+        assert!(moveset.forget(2).is_err());
+        assert_eq!(moveset.forget(1), Ok(Name::VineWhip));
+        assert!(moveset.forget(0).is_err());
+        assert_eq!(moveset.get_names(), [Name::Growl]);
+    }
+
+    #[test]
+    fn swapped_moves_keep_their_pp() {
+        let mut moveset = Moveset::new(&[Name::Tackle, Name::Growl]).unwrap();
+        moveset.get_mut(0).unwrap().use_power_points(5);
+        moveset.swap(0, 1).unwrap();
+        assert_eq!(moveset.get_names(), [Name::Growl, Name::Tackle]);
+        assert_eq!(moveset.get(1).unwrap().current_power_points, 30);
+        assert!(moveset.swap(0, 2).is_err());
+    }
+
+    #[test]
+    fn the_move_reminder_teaches_forgotten_level_up_moves() {
+        let mut pokemon = make_pokemon(1, 10);
+        pokemon.moveset = Moveset::new(&[Name::Tackle, Name::Growth]).unwrap();
+        assert_eq!(
+            pokemon.get_relearnable_moves(),
+            Ok(vec![Name::Growl, Name::VineWhip, Name::LeechSeed])
+        );
+        assert_eq!(
+            pokemon.relearn_move(Name::LeechSeed),
+            Ok(LearnOutcome::Learned(2))
+        );
+        // Razor Leaf is learned at level 12
+        assert!(pokemon.relearn_move(Name::RazorLeaf).is_err());
+    }
+}
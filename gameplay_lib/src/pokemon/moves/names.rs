@@ -3,9 +3,59 @@ use std::str::FromStr;
 use core_lib::utils::debug::ErrorCode;
 use log::error;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Name {
-    Tackle
+    Tackle,
+    Growl,
+    VineWhip,
+    Growth,
+    LeechSeed,
+    RazorLeaf,
+    PoisonPowder,
+    SleepPowder,
+    SeedBomb,
+    TakeDown,
+    SweetScent,
+    Synthesis,
+    WorrySeed,
+    PowerWhip,
+    SolarBeam,
+    Charm,
+    Protect,
+    AcidSpray,
+    Trailblaze,
+    Facade,
+    MagicalLeaf,
+    Venoshock,
+    Endure,
+    SunnyDay,
+    BulletSeed,
+    FalseSwipe,
+    BodySlam,
+    SleepTalk,
+    GrassKnot,
+    Rest,
+    SwordDance,
+    Substitute,
+    GigaDrain,
+    EnergyBall,
+    HelpingHand,
+    GrassyTerrain,
+    GrassPledge,
+    SludgeBomb,
+    LeafStorm,
+    TeraBlast,
+    Toxic,
+    KnockOff,
+    WeatherBall,
+    GrassyGlide,
+    DoubleEdge,
+    Curse,
+    Ingrain,
+    NaturePower,
+    PetalDance,
+    SkullBash,
+    Struggle,
     // TODO: Add more
 }
 
@@ -15,10 +65,60 @@ impl FromStr for Name {
     fn from_str(name: &str) -> Result<Self, ErrorCode> {
         match name {
             "Tackle" => Ok(Name::Tackle),
+            "Growl" => Ok(Name::Growl),
+            "VineWhip" => Ok(Name::VineWhip),
+            "Growth" => Ok(Name::Growth),
+            "LeechSeed" => Ok(Name::LeechSeed),
+            "RazorLeaf" => Ok(Name::RazorLeaf),
+            "PoisonPowder" => Ok(Name::PoisonPowder),
+            "SleepPowder" => Ok(Name::SleepPowder),
+            "SeedBomb" => Ok(Name::SeedBomb),
+            "TakeDown" => Ok(Name::TakeDown),
+            "SweetScent" => Ok(Name::SweetScent),
+            "Synthesis" => Ok(Name::Synthesis),
+            "WorrySeed" => Ok(Name::WorrySeed),
+            "PowerWhip" => Ok(Name::PowerWhip),
+            "SolarBeam" => Ok(Name::SolarBeam),
+            "Charm" => Ok(Name::Charm),
+            "Protect" => Ok(Name::Protect),
+            "AcidSpray" => Ok(Name::AcidSpray),
+            "Trailblaze" => Ok(Name::Trailblaze),
+            "Facade" => Ok(Name::Facade),
+            "MagicalLeaf" => Ok(Name::MagicalLeaf),
+            "Venoshock" => Ok(Name::Venoshock),
+            "Endure" => Ok(Name::Endure),
+            "SunnyDay" => Ok(Name::SunnyDay),
+            "BulletSeed" => Ok(Name::BulletSeed),
+            "FalseSwipe" => Ok(Name::FalseSwipe),
+            "BodySlam" => Ok(Name::BodySlam),
+            "SleepTalk" => Ok(Name::SleepTalk),
+            "GrassKnot" => Ok(Name::GrassKnot),
+            "Rest" => Ok(Name::Rest),
+            "SwordDance" => Ok(Name::SwordDance),
+            "Substitute" => Ok(Name::Substitute),
+            "GigaDrain" => Ok(Name::GigaDrain),
+            "EnergyBall" => Ok(Name::EnergyBall),
+            "HelpingHand" => Ok(Name::HelpingHand),
+            "GrassyTerrain" => Ok(Name::GrassyTerrain),
+            "GrassPledge" => Ok(Name::GrassPledge),
+            "SludgeBomb" => Ok(Name::SludgeBomb),
+            "LeafStorm" => Ok(Name::LeafStorm),
+            "TeraBlast" => Ok(Name::TeraBlast),
+            "Toxic" => Ok(Name::Toxic),
+            "KnockOff" => Ok(Name::KnockOff),
+            "WeatherBall" => Ok(Name::WeatherBall),
+            "GrassyGlide" => Ok(Name::GrassyGlide),
+            "DoubleEdge" => Ok(Name::DoubleEdge),
+            "Curse" => Ok(Name::Curse),
+            "Ingrain" => Ok(Name::Ingrain),
+            "NaturePower" => Ok(Name::NaturePower),
+            "PetalDance" => Ok(Name::PetalDance),
+            "SkullBash" => Ok(Name::SkullBash),
+            "Struggle" => Ok(Name::Struggle),
             _ => {
                 error!("The name {} is not a valid move name", name);
                 Err(ErrorCode::BadValue)
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,235 @@
+pub mod names;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+use names::Name;
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+
+use super::stats::{StatModifier, StatType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Spicy,
+    Dry,
+    Sweet,
+    Bitter,
+    Sour,
+}
+
+impl Flavor {
+    /// The flavor associated to a stat, the HP has none
+    pub fn from_stat(stat_type: StatType) -> Option<Self> {
+        match stat_type {
+            StatType::Hp => None,
+            StatType::Attack => Some(Flavor::Spicy),
+            StatType::Defense => Some(Flavor::Sour),
+            StatType::SpecialAttack => Some(Flavor::Dry),
+            StatType::SpecialDefense => Some(Flavor::Bitter),
+            StatType::Speed => Some(Flavor::Sweet),
+        }
+    }
+}
+
+/// A nature raising a stat by 10% and lowering another one by 10%
+/// Neutral natures raise and lower the same stat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nature {
+    pub name: Name,
+    pub raised_stat: StatType,
+    pub lowered_stat: StatType,
+}
+
+impl Nature {
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let name = Name::from_str(&Toml::get_string(toml, "name")?)?;
+        let raised_stat = StatType::from_str(&Toml::get_string(toml, "raised_stat")?)?;
+        let lowered_stat = StatType::from_str(&Toml::get_string(toml, "lowered_stat")?)?;
+        if raised_stat == StatType::Hp || lowered_stat == StatType::Hp {
+            error!("The nature {:?} can't modify the HP", name);
+            return Err(ErrorCode::BadValue);
+        }
+
+        Ok(Self {
+            name,
+            raised_stat,
+            lowered_stat,
+        })
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        self.raised_stat == self.lowered_stat
+    }
+
+    pub fn get_stat_modifier(&self, stat_type: StatType) -> StatModifier {
+        if self.is_neutral() {
+            StatModifier::Neutral
+        } else if stat_type == self.raised_stat {
+            StatModifier::Raised
+        } else if stat_type == self.lowered_stat {
+            StatModifier::Lowered
+        } else {
+            StatModifier::Neutral
+        }
+    }
+
+    /// The flavor of berries liked by a pokemon of this nature
+    pub fn get_liked_flavor(&self) -> Option<Flavor> {
+        if self.is_neutral() {
+            None
+        } else {
+            Flavor::from_stat(self.raised_stat)
+        }
+    }
+
+    /// The flavor of berries disliked by a pokemon of this nature
+    pub fn get_disliked_flavor(&self) -> Option<Flavor> {
+        if self.is_neutral() {
+            None
+        } else {
+            Flavor::from_stat(self.lowered_stat)
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Natures {
+    pub data: HashMap<Name, Nature>,
+}
+
+impl Natures {
+    async fn get_toml() -> Result<toml::Table, ErrorCode> {
+        let mut natures_toml_path = PathBuf::from("");
+        natures_toml_path.push("assets");
+        natures_toml_path.push("data");
+        natures_toml_path.push("natures");
+        natures_toml_path.set_extension("toml");
+
+        Toml::get_toml(&natures_toml_path).await
+    }
+
+    fn new() -> Result<Self, ErrorCode> {
+        let toml = match pollster::block_on(Self::get_toml()) {
+            Ok(toml) => toml,
+            Err(err) => {
+                error!("Failed to block on the Natures' toml: {:?}", err);
+                return Err(ErrorCode::Unknown);
+            }
+        };
+        let mut data: HashMap<Name, Nature> = Default::default();
+
+        let natures_array = match toml.get("natures").and_then(|natures| natures.as_array()) {
+            Some(natures_array) => natures_array,
+            None => {
+                error!("Failed to read the natures from the Natures' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        let natures_table = match natures_array.first().and_then(|natures| natures.as_table()) {
+            Some(natures_table) => natures_table,
+            None => {
+                error!("Failed to read the natures table from the Natures' toml");
+                return Err(ErrorCode::NotFound);
+            }
+        };
+        for (name, nature_table) in natures_table.iter() {
+            let nature = match nature_table.as_table().map(Nature::from_toml) {
+                Some(Ok(nature)) => nature,
+                Some(Err(err)) => {
+                    error!("Failed to create the nature {}: {:?}", name, err);
+                    return Err(ErrorCode::Unknown);
+                }
+                None => {
+                    error!("The nature {} is not a table in the Natures' toml", name);
+                    return Err(ErrorCode::BadValue);
+                }
+            };
+            if data.insert(nature.name, nature).is_some() {
+                error!("The nature {:?} is defined twice", nature.name);
+                return Err(ErrorCode::Duplicate);
+            }
+        }
+
+        if let Some(missing) = Name::ALL.iter().find(|name| !data.contains_key(name)) {
+            error!("The nature {:?} is missing from the Natures' toml", missing);
+            return Err(ErrorCode::NotFound);
+        }
+
+        Ok(Self { data })
+    }
+
+    pub fn get(&self, name: Name) -> Result<Nature, ErrorCode> {
+        match self.data.get(&name) {
+            Some(nature) => Ok(*nature),
+            None => {
+                error!("The nature {:?} is not in the natures table", name);
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+}
+
+static GLOBAL_NATURES: OnceLock<Arc<Natures>> = OnceLock::new();
+
+pub fn get_global_natures() -> Result<Arc<Natures>, ErrorCode> {
+    if let Some(natures) = GLOBAL_NATURES.get() {
+        return Ok(natures.clone());
+    }
+    let natures = match Natures::new() {
+        Ok(natures) => natures,
+        Err(err) => {
+            error!("Failed to initialize the global natures: {:?}", err);
+            return Err(ErrorCode::Unknown);
+        }
+    };
+    // Keeps the first value if another thread initialized it in the meantime
+    Ok(GLOBAL_NATURES.get_or_init(|| Arc::new(natures)).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::stats::{StatModifier, StatType};
+
+    use super::{get_global_natures, names::Name, Flavor};
+
+    #[test]
+    fn natures_raise_and_lower_their_stats() {
+        let adamant = get_global_natures().unwrap().get(Name::Adamant).unwrap();
+        assert_eq!(
+            adamant.get_stat_modifier(StatType::Attack),
+            StatModifier::Raised
+        );
+        assert_eq!(
+            adamant.get_stat_modifier(StatType::SpecialAttack),
+            StatModifier::Lowered
+        );
+        assert_eq!(
+            adamant.get_stat_modifier(StatType::Speed),
+            StatModifier::Neutral
+        );
+        assert_eq!(adamant.get_liked_flavor(), Some(Flavor::Spicy));
+        assert_eq!(adamant.get_disliked_flavor(), Some(Flavor::Dry));
+    }
+
+    #[test]
+    fn neutral_natures_change_nothing() {
+        let hardy = get_global_natures().unwrap().get(Name::Hardy).unwrap();
+        assert!(hardy.is_neutral());
+        assert_eq!(
+            hardy.get_stat_modifier(StatType::Attack),
+            StatModifier::Neutral
+        );
+        assert_eq!(hardy.get_liked_flavor(), None);
+    }
+
+    #[test]
+    fn every_nature_is_loaded() {
+        assert_eq!(get_global_natures().unwrap().data.len(), Name::ALL.len());
+    }
+}
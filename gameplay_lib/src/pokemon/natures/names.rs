@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Name {
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Name {
+    /// Every nature, ordered by their index in the games
+    pub const ALL: [Name; 25] = [
+        Name::Hardy,
+        Name::Lonely,
+        Name::Brave,
+        Name::Adamant,
+        Name::Naughty,
+        Name::Bold,
+        Name::Docile,
+        Name::Relaxed,
+        Name::Impish,
+        Name::Lax,
+        Name::Timid,
+        Name::Hasty,
+        Name::Serious,
+        Name::Jolly,
+        Name::Naive,
+        Name::Modest,
+        Name::Mild,
+        Name::Quiet,
+        Name::Bashful,
+        Name::Rash,
+        Name::Calm,
+        Name::Gentle,
+        Name::Sassy,
+        Name::Careful,
+        Name::Quirky,
+    ];
+}
+
+impl FromStr for Name {
+    type Err = ErrorCode;
+
+    fn from_str(name: &str) -> Result<Self, ErrorCode> {
+        match name {
+            "Hardy" => Ok(Name::Hardy),
+            "Lonely" => Ok(Name::Lonely),
+            "Brave" => Ok(Name::Brave),
+            "Adamant" => Ok(Name::Adamant),
+            "Naughty" => Ok(Name::Naughty),
+            "Bold" => Ok(Name::Bold),
+            "Docile" => Ok(Name::Docile),
+            "Relaxed" => Ok(Name::Relaxed),
+            "Impish" => Ok(Name::Impish),
+            "Lax" => Ok(Name::Lax),
+            "Timid" => Ok(Name::Timid),
+            "Hasty" => Ok(Name::Hasty),
+            "Serious" => Ok(Name::Serious),
+            "Jolly" => Ok(Name::Jolly),
+            "Naive" => Ok(Name::Naive),
+            "Modest" => Ok(Name::Modest),
+            "Mild" => Ok(Name::Mild),
+            "Quiet" => Ok(Name::Quiet),
+            "Bashful" => Ok(Name::Bashful),
+            "Rash" => Ok(Name::Rash),
+            "Calm" => Ok(Name::Calm),
+            "Gentle" => Ok(Name::Gentle),
+            "Sassy" => Ok(Name::Sassy),
+            "Careful" => Ok(Name::Careful),
+            "Quirky" => Ok(Name::Quirky),
+            _ => {
+                error!("The name {} is not a valid nature name", name);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use rand::Rng;
+
+use super::{natures::names::Name, Pokemon};
+
+/// Identifies the trainer who first caught or hatched a pokemon
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrainerId {
+    /// The public part, displayed on the trainer card
+    pub id: u16,
+    pub secret_id: u16,
+}
+
+impl TrainerId {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self {
+            id: rng.gen(),
+            secret_id: rng.gen(),
+        }
+    }
+}
+
+/// A personality value is shiny when its shiny value is below this threshold, 1 out of 4096
+pub const SHINY_THRESHOLD: u32 = 16;
+
+/// The chances for a pokemon to be shiny
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShinyOdds {
+    /// Each roll gives a new chance for the personality value to be shiny
+    pub nb_rolls: u8,
+    pub has_shiny_charm: bool,
+}
+
+impl Default for ShinyOdds {
+    fn default() -> Self {
+        Self {
+            nb_rolls: 1,
+            has_shiny_charm: false,
+        }
+    }
+}
+
+impl ShinyOdds {
+    /// Number of additional rolls given by the Shiny Charm
+    pub const SHINY_CHARM_ROLLS: u8 = 2;
+
+    pub fn get_nb_rolls(&self) -> u8 {
+        if self.has_shiny_charm {
+            self.nb_rolls.saturating_add(Self::SHINY_CHARM_ROLLS)
+        } else {
+            self.nb_rolls
+        }
+    }
+
+    /// Probability for a new pokemon to be shiny
+    pub fn get_probability(&self) -> f64 {
+        let miss_chance = 1. - SHINY_THRESHOLD as f64 / 0x10000 as f64;
+        1. - miss_chance.powi(self.get_nb_rolls() as i32)
+    }
+}
+
+/// Mixes the trainer ID and the personality value, the lower the rarer
+pub fn get_shiny_value(personality_value: u32, trainer: &TrainerId) -> u32 {
+    let high = (personality_value >> 16) as u16;
+    let low = personality_value as u16;
+    (trainer.id ^ trainer.secret_id ^ high ^ low) as u32
+}
+
+/// Rolls a personality value, rerolling it while it isn't shiny and rolls are left
+pub fn roll_personality_value<R: Rng>(
+    rng: &mut R,
+    trainer: &TrainerId,
+    shiny_odds: &ShinyOdds,
+) -> u32 {
+    let mut personality_value = rng.gen();
+    for _ in 1..shiny_odds.get_nb_rolls() {
+        if is_shiny(personality_value, trainer) {
+            break;
+        }
+        personality_value = rng.gen();
+    }
+    personality_value
+}
+
+pub fn is_shiny(personality_value: u32, trainer: &TrainerId) -> bool {
+    get_shiny_value(personality_value, trainer) < SHINY_THRESHOLD
+}
+
+pub fn get_nature_name(personality_value: u32) -> Name {
+    Name::ALL[(personality_value % Name::ALL.len() as u32) as usize]
+}
+
+/// Index of the ability among the species' abilities
+pub fn get_ability_slot(personality_value: u32) -> usize {
+    ((personality_value >> 16) & 1) as usize
+}
+
+impl Pokemon {
+    pub fn is_shiny(&self) -> bool {
+        is_shiny(self.personality_value, &self.original_trainer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::natures::names::Name;
+
+    use super::{
+        get_ability_slot, get_nature_name, get_shiny_value, is_shiny, roll_personality_value,
+        ShinyOdds, TrainerId,
+    };
+
+    const TRAINER: TrainerId = TrainerId {
+        id: 0x0001,
+        secret_id: 0x0002,
+    };
+
+    #[test]
+    fn shininess_mixes_the_trainer_and_the_personality_value() {
+        assert_eq!(get_shiny_value(0x5A3C_5A3C, &TRAINER), 3);
+        assert!(is_shiny(0x5A3C_5A3C, &TRAINER));
+        assert_eq!(get_shiny_value(0x5A3C_5A2C, &TRAINER), 0x13);
+        assert!(!is_shiny(0x5A3C_5A2C, &TRAINER));
+    }
+
+    #[test]
+    fn natures_and_abilities_come_from_the_personality_value() {
+        assert_eq!(get_nature_name(0), Name::Hardy);
+        assert_eq!(get_nature_name(28), Name::Adamant);
+        assert_eq!(get_ability_slot(0x0000_FFFF), 0);
+        assert_eq!(get_ability_slot(0x0001_0000), 1);
+    }
+
+    #[test]
+    fn shiny_odds_grow_with_the_rolls() {
+        let odds = ShinyOdds::default();
+        assert!((odds.get_probability() - 1. / 4096.).abs() < 1e-9);
+        let charm = ShinyOdds {
+            nb_rolls: 1,
+            has_shiny_charm: true,
+        };
+        assert_eq!(charm.get_nb_rolls(), 3);
+        assert!((charm.get_probability() - 3. / 4096.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rerolls_stop_on_a_shiny_value() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let odds = ShinyOdds {
+            nb_rolls: u8::MAX,
+            has_shiny_charm: false,
+        };
+        let nb_shinies = (0..1000)
+            .filter(|_| is_shiny(roll_personality_value(&mut rng, &TRAINER, &odds), &TRAINER))
+            .count();
+        // Each pokemon has about a 6% chance to be shiny
+        assert!((30..100).contains(&nb_shinies));
+    }
+}
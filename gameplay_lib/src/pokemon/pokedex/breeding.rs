@@ -1,23 +1,93 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
-use core_lib::utils::debug::ErrorCode;
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
 use log::error;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Gender {
     Male,
     Female,
     Neutral,
 }
 
-#[derive(Debug)]
-pub struct GenderRatio {
-    pub male: f32,
-    pub female: f32,
-    pub neutral: f32,
+impl FromStr for Gender {
+    fn from_str(gender: &str) -> Result<Self, ErrorCode> {
+        match gender {
+            "Male" => Ok(Gender::Male),
+            "Female" => Ok(Gender::Female),
+            "Neutral" => Ok(Gender::Neutral),
+            _ => {
+                error!("The gender {} is not a valid pokemon gender", gender);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+
+    type Err = ErrorCode;
 }
 
-#[derive(Debug, Clone, Copy)]
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gender::Male => write!(f, "♂"),
+            Gender::Female => write!(f, "♀"),
+            Gender::Neutral => Ok(()),
+        }
+    }
+}
+
+pub const GENDER_RATIO_DENOMINATOR: u8 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenderRatio {
+    Genderless,
+    /// Number of females out of `GENDER_RATIO_DENOMINATOR` pokemons
+    Female(u8),
+}
+
+impl GenderRatio {
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        if toml.is_empty() {
+            return Ok(GenderRatio::Genderless);
+        }
+        let female = Toml::get_u8(toml, "female")?;
+        if female > GENDER_RATIO_DENOMINATOR {
+            error!(
+                "The gender ratio {}/{} is not a valid ratio",
+                female, GENDER_RATIO_DENOMINATOR
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(GenderRatio::Female(female))
+    }
+
+    /// Probability for a pokemon of this species to be a female
+    pub fn get_female_chance(&self) -> f32 {
+        match self {
+            GenderRatio::Genderless => 0.,
+            GenderRatio::Female(female) => *female as f32 / GENDER_RATIO_DENOMINATOR as f32,
+        }
+    }
+
+    /// The lowest byte of the personality value is compared to a threshold matching the ratio
+    pub fn get_gender(&self, personality_value: u32) -> Gender {
+        let threshold = match self {
+            GenderRatio::Genderless => return Gender::Neutral,
+            GenderRatio::Female(0) => return Gender::Male,
+            GenderRatio::Female(GENDER_RATIO_DENOMINATOR) => return Gender::Female,
+            GenderRatio::Female(female) => {
+                (*female as u32 * 0x100 / GENDER_RATIO_DENOMINATOR as u32).saturating_sub(1)
+            }
+        };
+        if (personality_value & 0xFF) < threshold {
+            Gender::Female
+        } else {
+            Gender::Male
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EggGroup {
     Monster,
     HumanLike,
@@ -67,4 +137,36 @@ impl FromStr for EggGroup {
     type Err = ErrorCode;
 }
 
-pub const NB_STEPS_PER_EGG_CYCLE: u8 = 0xFF;
\ No newline at end of file
+pub const NB_STEPS_PER_EGG_CYCLE: u8 = 0xFF;
+
+#[cfg(test)]
+mod tests {
+    use super::{Gender, GenderRatio};
+
+    #[test]
+    fn ratios_are_read_in_eighths() {
+        let toml = "female = 1".parse::<toml::Table>().unwrap();
+        let ratio = GenderRatio::from_toml(&toml).unwrap();
+        assert_eq!(ratio, GenderRatio::Female(1));
+        assert_eq!(ratio.get_female_chance(), 0.125);
+
+        let toml = toml::Table::new();
+        assert_eq!(
+            GenderRatio::from_toml(&toml).unwrap(),
+            GenderRatio::Genderless
+        );
+        let toml = "female = 9".parse::<toml::Table>().unwrap();
+        assert!(GenderRatio::from_toml(&toml).is_err());
+    }
+
+    #[test]
+    fn genders_come_from_the_personality_value() {
+        assert_eq!(GenderRatio::Female(1).get_gender(30), Gender::Female);
+        assert_eq!(GenderRatio::Female(1).get_gender(31), Gender::Male);
+        assert_eq!(GenderRatio::Female(4).get_gender(0x1_7E), Gender::Female);
+        assert_eq!(GenderRatio::Female(4).get_gender(0x1_7F), Gender::Male);
+        assert_eq!(GenderRatio::Female(0).get_gender(0), Gender::Male);
+        assert_eq!(GenderRatio::Female(8).get_gender(0xFF), Gender::Female);
+        assert_eq!(GenderRatio::Genderless.get_gender(0), Gender::Neutral);
+    }
+}
@@ -3,14 +3,16 @@ use std::{fmt, path::PathBuf, str::FromStr};
 use core_lib::utils::{debug::ErrorCode, toml::Toml};
 use log::error;
 
-use crate::pokemon::{abilities, evolutions::EvolutionType, stats::Stats, types::Type};
+use crate::pokemon::{abilities, evolutions::Evolution, stats::Stats, types::Type};
 
 use super::{
-    breeding::{EggGroup, GenderRatio}, categories::Category, names::Name,
+    breeding::{EggGroup, GenderRatio},
+    categories::Category,
+    experience_group::ExperienceGroup,
+    names::Name,
     Id,
 };
 
-
 pub struct PokedexEntry {
     pub pokedex_number: Id,
 
@@ -29,19 +31,20 @@ pub struct PokedexEntry {
     pub type_1: Type,
     pub type_2: Option<Type>,
     pub previous_evolution: Option<Id>,
-    pub next_evolution: Option<Id>,
-    pub evolution_type: Option<EvolutionType>,
+    /// Every species this one can evolve into, with their conditions
+    pub evolutions: Vec<Evolution>,
     pub base_stats: Stats,
 
     pub egg_group_1: EggGroup,
     pub egg_group_2: Option<EggGroup>,
     pub hatch_time: u8,
     pub gender_ratio: GenderRatio,
-    
+
     pub abilities: Vec<abilities::names::Name>,
     pub catch_rate: u8,
     pub base_friendship: u8,
     pub base_experience_yield: u32,
+    pub experience_group: ExperienceGroup,
     pub ev_yield: Stats,
 }
 
@@ -61,8 +64,7 @@ impl fmt::Debug for PokedexEntry {
 \ttype_1: {:?},
 \ttype_2: {:?},
 \tprevious_evolution: {:?},
-\tnext_evolution: {:?},
-\tevolution_type: {:?},
+\tevolutions: {:?},
 \tegg_group_1: {:?},
 \tegg_group_2: {:?},
 \thatch_time: {:?},
@@ -71,6 +73,7 @@ impl fmt::Debug for PokedexEntry {
 \tcatch_rate: {:?},
 \tbase_friendship: {:?},
 \tbase_experience_yield: {:?},
+\texperience_group: {:?},
 \tev_yield: {:?},
 \tbase_stats: {:?},
 }}",
@@ -85,8 +88,7 @@ impl fmt::Debug for PokedexEntry {
             self.type_1,
             self.type_2,
             self.previous_evolution,
-            self.next_evolution,
-            self.evolution_type,
+            self.evolutions,
             self.egg_group_1,
             self.egg_group_2,
             self.hatch_time,
@@ -95,6 +97,7 @@ impl fmt::Debug for PokedexEntry {
             self.catch_rate,
             self.base_friendship,
             self.base_experience_yield,
+            self.experience_group,
             self.ev_yield,
             self.base_stats,
         )
@@ -102,6 +105,15 @@ impl fmt::Debug for PokedexEntry {
 }
 
 impl PokedexEntry {
+    /// Multiplier of an attack of the given type against this pokemon's types
+    pub fn get_effectiveness_from(&self, attacking_type: &Type) -> f32 {
+        attacking_type.get_dual_effectiveness(&self.type_1, self.type_2.as_ref())
+    }
+
+    pub fn has_type(&self, element_type: &Type) -> bool {
+        self.type_1 == *element_type || self.type_2.as_ref() == Some(element_type)
+    }
+
     fn get_pokedex_number(toml: &toml::Table) -> Result<Id, ErrorCode> {
         Ok(Toml::get_u16(toml, "id")? as Id)
     }
@@ -181,14 +193,7 @@ impl PokedexEntry {
 
     fn get_gender_ratio(toml: &toml::Table) -> Result<GenderRatio, ErrorCode> {
         let gender_ratio = Toml::get_table(toml, "gender_ratio")?;
-        let male_ratio = Toml::get_f32(gender_ratio, "male")?;
-        let female_ratio = Toml::get_f32(gender_ratio, "female")?;
-        let neutral_ratio = Toml::get_f32(gender_ratio, "neutral")?;
-        Ok(GenderRatio {
-            male: male_ratio,
-            female: female_ratio,
-            neutral: neutral_ratio,
-        })
+        GenderRatio::from_toml(gender_ratio)
     }
 
     fn get_abilities(toml: &toml::Table) -> Result<Vec<abilities::names::Name>, ErrorCode> {
@@ -211,6 +216,11 @@ impl PokedexEntry {
         Toml::get_u32(toml, "base_experience_yield")
     }
 
+    fn get_experience_group(toml: &toml::Table) -> Result<ExperienceGroup, ErrorCode> {
+        let experience_group = Toml::get_string(toml, "experience_group")?;
+        ExperienceGroup::from_str(&experience_group)
+    }
+
     fn get_evs_yield(toml: &toml::Table) -> Result<Stats, ErrorCode> {
         let ev_yield = Toml::get_table(toml, "ev_yield")?;
         Stats::from_toml(ev_yield)
@@ -225,22 +235,11 @@ impl PokedexEntry {
         }
     }
 
-    fn get_next_evolution(toml: &toml::Table) -> Result<Option<Id>, ErrorCode> {
-        let id = Toml::get_u16(toml, "next_evolution")? as Id;
-        if id == 0 {
-            Ok(None)
-        } else {
-            Ok(Some(id))
-        }
-    }
-
-    fn get_evolution_type(toml: &toml::Table) -> Result<Option<EvolutionType>, ErrorCode> {
-        let evolution_type_table = Toml::get_table(toml, "evolution_type")?;
-        if evolution_type_table.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(EvolutionType::from_toml(evolution_type_table)?))
-        }
+    fn get_evolutions(toml: &toml::Table) -> Result<Vec<Evolution>, ErrorCode> {
+        Toml::get_table_list(toml, "evolution_type")?
+            .into_iter()
+            .map(Evolution::from_toml)
+            .collect()
     }
 
     pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
@@ -254,8 +253,7 @@ impl PokedexEntry {
         let shiny_sprites = Self::get_shiny_sprites(toml)?;
         let (type_1, type_2) = Self::get_types(toml)?;
         let previous_evolution = Self::get_previous_evolution(toml)?;
-        let next_evolution = Self::get_next_evolution(toml)?;
-        let evolution_type = Self::get_evolution_type(toml)?;
+        let evolutions = Self::get_evolutions(toml)?;
         let (egg_group_1, egg_group_2) = Self::get_egg_groups(toml)?;
         let hatch_time = Self::get_hatch_time(toml)?;
         let gender_ratio = Self::get_gender_ratio(toml)?;
@@ -263,6 +261,7 @@ impl PokedexEntry {
         let catch_rate = Self::get_catch_rate(toml)?;
         let base_friendship = Self::get_base_friendship(toml)?;
         let base_experience_yield = Self::get_base_experience_yield(toml)?;
+        let experience_group = Self::get_experience_group(toml)?;
         let ev_yield = Self::get_evs_yield(toml)?;
         let base_stats = Self::get_base_stats(toml)?;
 
@@ -278,8 +277,7 @@ impl PokedexEntry {
             type_1,
             type_2,
             previous_evolution,
-            next_evolution,
-            evolution_type,
+            evolutions,
             base_stats,
             egg_group_1,
             egg_group_2,
@@ -289,6 +287,7 @@ impl PokedexEntry {
             catch_rate,
             base_friendship,
             base_experience_yield,
+            experience_group,
             ev_yield,
         })
     }
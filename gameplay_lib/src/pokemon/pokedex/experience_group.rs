@@ -1,4 +1,11 @@
-#[derive(Debug)]
+use std::str::FromStr;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+
+pub const MAX_LEVEL: u8 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExperienceGroup {
     Erratic,
     Fast,
@@ -8,12 +15,35 @@ pub enum ExperienceGroup {
     Fluctuating,
 }
 
+impl FromStr for ExperienceGroup {
+    fn from_str(group: &str) -> Result<Self, ErrorCode> {
+        match group {
+            "Erratic" => Ok(ExperienceGroup::Erratic),
+            "Fast" => Ok(ExperienceGroup::Fast),
+            "MediumFast" => Ok(ExperienceGroup::MediumFast),
+            "MediumSlow" => Ok(ExperienceGroup::MediumSlow),
+            "Slow" => Ok(ExperienceGroup::Slow),
+            "Fluctuating" => Ok(ExperienceGroup::Fluctuating),
+            _ => {
+                error!(
+                    "The experience group {} is not a valid pokemon experience group",
+                    group
+                );
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+
+    type Err = ErrorCode;
+}
+
+/// The total amount of experience needed to reach the given level
 pub fn get_experience_from_group(group: ExperienceGroup, level: u8) -> u32 {
-    if level == 100 {
+    if level <= 1 {
         return 0;
     }
 
-    let n = level as f64;
+    let n = level.min(MAX_LEVEL) as f64;
     let n_2 = n * n;
     let n_3 = n_2 * n;
     match group {
@@ -43,3 +73,72 @@ pub fn get_experience_from_group(group: ExperienceGroup, level: u8) -> u32 {
         }
     }
 }
+
+/// The level reached with the given total amount of experience
+pub fn get_level_from_experience(group: ExperienceGroup, experience: u32) -> u8 {
+    let mut level = 1;
+    while level < MAX_LEVEL && get_experience_from_group(group, level + 1) <= experience {
+        level += 1;
+    }
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_experience_from_group, get_level_from_experience, ExperienceGroup};
+
+    #[test]
+    fn experience_at_the_maximum_level() {
+        let expected = [
+            (ExperienceGroup::Erratic, 600_000),
+            (ExperienceGroup::Fast, 800_000),
+            (ExperienceGroup::MediumFast, 1_000_000),
+            (ExperienceGroup::MediumSlow, 1_059_860),
+            (ExperienceGroup::Slow, 1_250_000),
+            (ExperienceGroup::Fluctuating, 1_640_000),
+        ];
+        for (group, experience) in expected {
+            assert_eq!(
+                get_experience_from_group(group, 100),
+                experience,
+                "{:?}",
+                group
+            );
+        }
+    }
+
+    #[test]
+    fn experience_at_intermediate_levels() {
+        assert_eq!(get_experience_from_group(ExperienceGroup::MediumSlow, 1), 0);
+        assert_eq!(get_experience_from_group(ExperienceGroup::MediumSlow, 2), 9);
+        assert_eq!(
+            get_experience_from_group(ExperienceGroup::MediumSlow, 50),
+            117_360
+        );
+        assert_eq!(
+            get_experience_from_group(ExperienceGroup::Erratic, 50),
+            125_000
+        );
+        assert_eq!(
+            get_experience_from_group(ExperienceGroup::Fluctuating, 50),
+            142_500
+        );
+    }
+
+    #[test]
+    fn level_reached_with_some_experience() {
+        assert_eq!(get_level_from_experience(ExperienceGroup::MediumFast, 0), 1);
+        assert_eq!(
+            get_level_from_experience(ExperienceGroup::MediumFast, 124_999),
+            49
+        );
+        assert_eq!(
+            get_level_from_experience(ExperienceGroup::MediumFast, 125_000),
+            50
+        );
+        assert_eq!(
+            get_level_from_experience(ExperienceGroup::MediumFast, u32::MAX),
+            100
+        );
+    }
+}
@@ -10,7 +10,11 @@ use entry::PokedexEntry;
 
 use log::error;
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
 
 pub type Id = u16;
 
@@ -41,14 +45,14 @@ impl Pokedex {
         };
         let mut data: HashMap<Id, PokedexEntry> = Default::default();
 
-        let pokedex_array = match toml["pokedex"].as_array(){
+        let pokedex_array = match toml["pokedex"].as_array() {
             Some(pokedex_array) => pokedex_array,
             None => {
                 error!("Failed to read the pokedex from the Pokedex's toml");
                 return Err(ErrorCode::NotFound);
             }
         };
-        let pokedex_table = match pokedex_array[0].as_table(){
+        let pokedex_table = match pokedex_array[0].as_table() {
             Some(pokedex_table) => pokedex_table,
             None => {
                 error!("Failed to read the pokedex table from the Pokedex's toml");
@@ -57,7 +61,7 @@ impl Pokedex {
         };
         let mut id = 0;
         for (name, pokemon_table) in pokedex_table.iter() {
-            id+=1;
+            id += 1;
             let pokemon_table = pokemon_table.as_table().unwrap();
             let pokemon_entry: PokedexEntry = match PokedexEntry::from_toml(pokemon_table) {
                 Ok(new_pokemon) => new_pokemon,
@@ -73,7 +77,7 @@ impl Pokedex {
             };
             data.insert(pokemon_entry.pokedex_number, pokemon_entry);
         }
-        Ok(Self{data})
+        Ok(Self { data })
 
         // for (id, value) in pokedex_array.iter().enumerate() {
         //     let (name, table) = match value.as_table() {
@@ -118,22 +122,33 @@ impl Pokedex {
     }
 }
 
-static mut GLOBAL_POKEDEX: Option<Arc<Pokedex>> = None;
+static GLOBAL_POKEDEX: OnceLock<Arc<Pokedex>> = OnceLock::new();
+
+fn get_static_pokedex() -> Result<&'static Arc<Pokedex>, ErrorCode> {
+    if let Some(pokedex) = GLOBAL_POKEDEX.get() {
+        return Ok(pokedex);
+    }
+    let pokedex = match Pokedex::new() {
+        Ok(pokedex) => pokedex,
+        Err(err) => {
+            error!("Failed to initialize the global pokedex: {:?}", err);
+            return Err(ErrorCode::Unknown);
+        }
+    };
+    // Keeps the first value if another thread initialized it in the meantime
+    Ok(GLOBAL_POKEDEX.get_or_init(|| Arc::new(pokedex)))
+}
 
 pub fn get_global_pokedex() -> Result<Arc<Pokedex>, ErrorCode> {
-    let state = unsafe { GLOBAL_POKEDEX.clone() };
-    match state {
-        Some(state) => Ok(state),
+    get_static_pokedex().cloned()
+}
+
+pub fn get_global_pokedex_entry(id: Id) -> Result<&'static PokedexEntry, ErrorCode> {
+    match get_static_pokedex()?.data.get(&id) {
+        Some(entry) => Ok(entry),
         None => {
-            let pokedex = match Pokedex::new() {
-                Ok(pokedex) => pokedex,
-                Err(err) => {
-                    error!("Failed to initialize the global pokedex: {:?}", err);
-                    return Err(ErrorCode::Unknown);
-                }
-            };
-            unsafe { GLOBAL_POKEDEX = Some(Arc::new(pokedex)) };
-            get_global_pokedex()
+            error!("The pokemon #{:0>4} is not in the global pokedex", id);
+            Err(ErrorCode::NotFound)
         }
     }
 }
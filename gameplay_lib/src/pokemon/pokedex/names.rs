@@ -3,7 +3,7 @@ use std::str::FromStr;
 use core_lib::utils::debug::ErrorCode;
 use log::error;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Name {
     Bulbasaur,
     Ivysaur,
@@ -0,0 +1,186 @@
+use super::{
+    pokedex::experience_group::MAX_LEVEL,
+    stats::{StatType, Stats},
+    Pokemon,
+};
+
+pub const MAX_EVS_PER_STAT: u16 = 252;
+pub const MAX_EVS_TOTAL: u16 = 510;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TradeStatus {
+    #[default]
+    Original,
+    /// Received from a trainer using the same game language
+    Domestic,
+    /// Received from a trainer using another game language
+    International,
+}
+
+impl TradeStatus {
+    fn get_multiplier(&self) -> f64 {
+        match self {
+            TradeStatus::Original => 1.,
+            TradeStatus::Domestic => 1.5,
+            TradeStatus::International => 1.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpShareMode {
+    /// Only the pokemons which battled get rewards, the experience is split between them
+    #[default]
+    Off,
+    /// The whole party gets rewards, the ones which did not battle get half the experience
+    On,
+}
+
+/// A non fainted pokemon of the party which may receive rewards
+pub struct Participant<'a> {
+    pub pokemon: &'a Pokemon,
+    /// Tells if the pokemon has been sent out against the defeated one
+    pub has_battled: bool,
+    pub trade_status: TradeStatus,
+    pub holds_lucky_egg: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reward {
+    pub experience: u32,
+    pub evs: Stats,
+}
+
+/// Computes the rewards gained by each participant when an opposing pokemon faints
+/// The rewards are in the same order as the participants
+pub fn get_rewards(
+    defeated: &Pokemon,
+    participants: &[Participant],
+    is_trainer_battle: bool,
+    exp_share_mode: ExpShareMode,
+) -> Vec<Reward> {
+    let nb_battled = participants.iter().filter(|p| p.has_battled).count() as u32;
+
+    participants
+        .iter()
+        .map(|participant| {
+            let nb_sharing = match (exp_share_mode, participant.has_battled) {
+                (ExpShareMode::Off, true) => nb_battled,
+                (ExpShareMode::Off, false) => return Reward::default(),
+                (ExpShareMode::On, true) => 1,
+                (ExpShareMode::On, false) => 2,
+            };
+            Reward {
+                experience: get_experience_reward(
+                    defeated,
+                    participant,
+                    nb_sharing,
+                    is_trainer_battle,
+                ),
+                evs: get_capped_evs(&participant.pokemon.evs, &defeated.pokedex_entry.ev_yield),
+            }
+        })
+        .collect()
+}
+
+/// Scaled experience formula
+/// The experience is divided by the number of pokemons sharing it
+fn get_experience_reward(
+    defeated: &Pokemon,
+    participant: &Participant,
+    nb_sharing: u32,
+    is_trainer_battle: bool,
+) -> u32 {
+    if participant.pokemon.level >= MAX_LEVEL || nb_sharing == 0 {
+        return 0;
+    }
+
+    let mut experience = get_scaled_experience(
+        defeated.pokedex_entry.base_experience_yield,
+        defeated.level,
+        participant.pokemon.level,
+        nb_sharing,
+    ) as f64;
+
+    if is_trainer_battle {
+        experience = (experience * 1.5).floor();
+    }
+    experience = (experience * participant.trade_status.get_multiplier()).floor();
+    if participant.holds_lucky_egg {
+        experience = (experience * 1.5).floor();
+    }
+
+    experience as u32
+}
+
+/// Experience before the bonuses, beating a higher level pokemon gives more experience
+fn get_scaled_experience(
+    base_yield: u32,
+    defeated_level: u8,
+    participant_level: u8,
+    nb_sharing: u32,
+) -> u32 {
+    let defeated_level = defeated_level as f64;
+    let participant_level = participant_level as f64;
+
+    let base = (base_yield as f64 * defeated_level) / (5. * nb_sharing as f64);
+    let scaling =
+        ((2. * defeated_level + 10.) / (defeated_level + participant_level + 10.)).powf(2.5);
+    (base * scaling).floor() as u32 + 1
+}
+
+/// The EVs which can actually be gained given the current ones
+pub fn get_capped_evs(current: &Stats, gained: &Stats) -> Stats {
+    let mut total = current.total();
+    let mut capped = Stats::default();
+    for stat_type in StatType::ALL {
+        let room_in_stat = MAX_EVS_PER_STAT.saturating_sub(current.get(stat_type));
+        let room_in_total = MAX_EVS_TOTAL.saturating_sub(total);
+        let value = gained.get(stat_type).min(room_in_stat).min(room_in_total);
+        capped.set(stat_type, value);
+        total += value;
+    }
+    capped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::stats::Stats;
+
+    use super::{get_capped_evs, get_scaled_experience};
+
+    #[test]
+    fn experience_scales_with_the_levels() {
+        assert_eq!(get_scaled_experience(64, 5, 5, 1), 65);
+        assert_eq!(get_scaled_experience(64, 50, 30, 1), 1057);
+        assert_eq!(get_scaled_experience(64, 50, 70, 1), 422);
+        assert_eq!(get_scaled_experience(64, 5, 5, 2), 33);
+    }
+
+    #[test]
+    fn evs_are_capped_per_stat_and_in_total() {
+        let current = Stats {
+            hp: 250,
+            attack: 252,
+            speed: 6,
+            ..Default::default()
+        };
+        let gained = Stats {
+            hp: 3,
+            attack: 3,
+            defense: 3,
+            ..Default::default()
+        };
+        let capped = get_capped_evs(&current, &gained);
+        assert_eq!((capped.hp, capped.attack, capped.defense), (2, 0, 0));
+
+        let current = Stats {
+            hp: 252,
+            attack: 252,
+            speed: 4,
+            ..Default::default()
+        };
+        let capped = get_capped_evs(&current, &gained);
+        assert_eq!((capped.hp, capped.attack, capped.defense), (0, 0, 2));
+    }
+}
@@ -1,5 +1,12 @@
+use std::str::FromStr;
+
 use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+use rand::Rng;
+
+pub const MAX_IV: u16 = 31;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StatType {
     Hp,
     Attack,
@@ -9,7 +16,37 @@ pub enum StatType {
     Speed,
 }
 
-#[derive(Debug)]
+impl StatType {
+    pub const ALL: [StatType; 6] = [
+        StatType::Hp,
+        StatType::Attack,
+        StatType::Defense,
+        StatType::SpecialAttack,
+        StatType::SpecialDefense,
+        StatType::Speed,
+    ];
+}
+
+impl FromStr for StatType {
+    type Err = ErrorCode;
+
+    fn from_str(stat_type: &str) -> Result<Self, ErrorCode> {
+        match stat_type {
+            "Hp" => Ok(StatType::Hp),
+            "Attack" => Ok(StatType::Attack),
+            "Defense" => Ok(StatType::Defense),
+            "SpecialAttack" => Ok(StatType::SpecialAttack),
+            "SpecialDefense" => Ok(StatType::SpecialDefense),
+            "Speed" => Ok(StatType::Speed),
+            _ => {
+                error!("The stat {} is not a valid stat type", stat_type);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Stats {
     pub hp: u16,
     pub attack: u16,
@@ -37,4 +74,163 @@ impl Stats {
             speed,
         })
     }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        for (key, stat_type) in [
+            ("hp", StatType::Hp),
+            ("attack", StatType::Attack),
+            ("defense", StatType::Defense),
+            ("special_attack", StatType::SpecialAttack),
+            ("special_defense", StatType::SpecialDefense),
+            ("speed", StatType::Speed),
+        ] {
+            toml.insert(key.to_string(), i64::from(self.get(stat_type)).into());
+        }
+        toml
+    }
+
+    pub fn get(&self, stat_type: StatType) -> u16 {
+        match stat_type {
+            StatType::Hp => self.hp,
+            StatType::Attack => self.attack,
+            StatType::Defense => self.defense,
+            StatType::SpecialAttack => self.special_attack,
+            StatType::SpecialDefense => self.special_defense,
+            StatType::Speed => self.speed,
+        }
+    }
+
+    pub fn set(&mut self, stat_type: StatType, value: u16) {
+        match stat_type {
+            StatType::Hp => self.hp = value,
+            StatType::Attack => self.attack = value,
+            StatType::Defense => self.defense = value,
+            StatType::SpecialAttack => self.special_attack = value,
+            StatType::SpecialDefense => self.special_defense = value,
+            StatType::Speed => self.speed = value,
+        }
+    }
+
+    /// Random individual values between 0 and `MAX_IV`
+    pub fn random_ivs<R: Rng>(rng: &mut R) -> Self {
+        let mut ivs = Self::default();
+        for stat_type in StatType::ALL {
+            ivs.set(stat_type, rng.gen_range(0..=MAX_IV));
+        }
+        ivs
+    }
+
+    pub fn total(&self) -> u16 {
+        StatType::ALL
+            .iter()
+            .map(|stat_type| self.get(*stat_type))
+            .sum()
+    }
+}
+
+/// Effect of a nature on a single stat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatModifier {
+    Lowered,
+    #[default]
+    Neutral,
+    Raised,
+}
+
+impl StatModifier {
+    pub fn get_multiplier(&self) -> f32 {
+        match self {
+            StatModifier::Lowered => 0.9,
+            StatModifier::Neutral => 1.,
+            StatModifier::Raised => 1.1,
+        }
+    }
+
+    /// Applies the modifier using integer arithmetic to avoid float rounding issues
+    pub fn apply(&self, value: u16) -> u16 {
+        let percentage = match self {
+            StatModifier::Lowered => 90,
+            StatModifier::Neutral => 100,
+            StatModifier::Raised => 110,
+        };
+        ((value as u32 * percentage) / 100) as u16
+    }
+}
+
+/// Every value taking part in the computation of a single stat
+/// Useful to display where a stat comes from
+#[derive(Debug, Clone, Copy)]
+pub struct StatBreakdown {
+    pub stat_type: StatType,
+    pub base: u16,
+    pub iv: u16,
+    pub ev: u16,
+    pub level: u8,
+    pub modifier: StatModifier,
+    pub value: u16,
+}
+
+impl StatBreakdown {
+    pub fn new(
+        stat_type: StatType,
+        base: u16,
+        iv: u16,
+        ev: u16,
+        level: u8,
+        modifier: StatModifier,
+    ) -> Self {
+        // The nature never affects the HP
+        let modifier = match stat_type {
+            StatType::Hp => StatModifier::Neutral,
+            _ => modifier,
+        };
+        let core = ((2 * base as u32 + iv as u32 + ev as u32 / 4) * level as u32) / 100;
+        let value = match stat_type {
+            StatType::Hp => (core + level as u32 + 10) as u16,
+            _ => modifier.apply((core + 5) as u16),
+        };
+
+        Self {
+            stat_type,
+            base,
+            iv,
+            ev,
+            level,
+            modifier,
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StatBreakdown, StatModifier, StatType};
+
+    /// The level 78 Garchomp from the stat formula examples
+    fn get_garchomp_stat(stat_type: StatType, base: u16, iv: u16, ev: u16) -> u16 {
+        let modifier = match stat_type {
+            StatType::Attack => StatModifier::Raised,
+            StatType::SpecialAttack => StatModifier::Lowered,
+            _ => StatModifier::Neutral,
+        };
+        StatBreakdown::new(stat_type, base, iv, ev, 78, modifier).value
+    }
+
+    #[test]
+    fn stats_follow_the_formula() {
+        assert_eq!(get_garchomp_stat(StatType::Hp, 108, 24, 74), 289);
+        assert_eq!(get_garchomp_stat(StatType::Attack, 130, 12, 190), 278);
+        assert_eq!(get_garchomp_stat(StatType::Defense, 95, 30, 91), 193);
+        assert_eq!(get_garchomp_stat(StatType::SpecialAttack, 80, 16, 48), 135);
+        assert_eq!(get_garchomp_stat(StatType::SpecialDefense, 85, 23, 84), 171);
+        assert_eq!(get_garchomp_stat(StatType::Speed, 102, 5, 23), 171);
+    }
+
+    #[test]
+    fn natures_never_affect_the_hp() {
+        let breakdown = StatBreakdown::new(StatType::Hp, 108, 24, 74, 78, StatModifier::Raised);
+        assert_eq!(breakdown.modifier, StatModifier::Neutral);
+        assert_eq!(breakdown.value, 289);
+    }
 }
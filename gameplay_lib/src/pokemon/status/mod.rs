@@ -0,0 +1,330 @@
+use std::str::FromStr;
+
+use core_lib::utils::debug::ErrorCode;
+use log::error;
+use rand::Rng;
+
+use super::{
+    field::{Field, Terrain},
+    types::Type,
+    Pokemon,
+};
+
+pub mod volatile;
+
+/// Number of turns a pokemon sleeps after using Rest
+pub const REST_SLEEP_TURNS: u8 = 2;
+
+/// Chance for a frozen pokemon to thaw before moving
+pub const THAW_CHANCE: f64 = 0.2;
+
+/// Chance for a paralyzed pokemon to be unable to move
+pub const FULL_PARALYSIS_CHANCE: f64 = 0.25;
+
+/// Status kept outside of battles, a pokemon has at most one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Burn,
+    Freeze,
+    Paralysis,
+    Poison,
+    /// Number of end of turn damages already taken
+    BadlyPoisoned(u8),
+    /// Number of turns left before waking up, the pokemon can't move during these turns
+    Sleep(u8),
+}
+
+impl Status {
+    /// Sleep lasting between one and three turns
+    pub fn random_sleep<R: Rng>(rng: &mut R) -> Self {
+        Status::Sleep(rng.gen_range(1..=3))
+    }
+
+    /// Types preventing a pokemon from getting the status
+    fn get_immune_types(&self) -> &'static [Type] {
+        match self {
+            Status::Burn => &[Type::Fire],
+            Status::Freeze => &[Type::Ice],
+            Status::Paralysis => &[Type::Electric],
+            Status::Poison | Status::BadlyPoisoned(_) => &[Type::Poison, Type::Steel],
+            Status::Sleep(_) => &[],
+        }
+    }
+}
+
+/// The counters start at 0, the length of a sleep is rolled when it is inflicted
+impl FromStr for Status {
+    type Err = ErrorCode;
+
+    fn from_str(status: &str) -> Result<Self, ErrorCode> {
+        match status {
+            "Burn" => Ok(Status::Burn),
+            "Freeze" => Ok(Status::Freeze),
+            "Paralysis" => Ok(Status::Paralysis),
+            "Poison" => Ok(Status::Poison),
+            "BadlyPoisoned" => Ok(Status::BadlyPoisoned(0)),
+            "Sleep" => Ok(Status::Sleep(0)),
+            _ => {
+                error!("The status {} is not a valid status", status);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// What happens to a pokemon trying to use a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeforeMoveOutcome {
+    CanMove,
+    WokeUp,
+    Thawed,
+    SnappedOutOfConfusion,
+    FastAsleep,
+    Frozen,
+    FullyParalyzed,
+    Flinched,
+    /// The battle engine deals the confusion damage
+    HurtItselfInConfusion,
+}
+
+impl BeforeMoveOutcome {
+    pub fn can_move(&self) -> bool {
+        matches!(
+            self,
+            BeforeMoveOutcome::CanMove
+                | BeforeMoveOutcome::WokeUp
+                | BeforeMoveOutcome::Thawed
+                | BeforeMoveOutcome::SnappedOutOfConfusion
+        )
+    }
+}
+
+impl Pokemon {
+    /// Tries to inflict the status, fails if the pokemon already has one or is immune to it
+    /// Outside of battles, the field is simply empty
+    pub fn try_set_status(&mut self, status: Status, field: &Field) -> bool {
+        if self.status.is_some() || self.is_fainted() {
+            return false;
+        }
+        let entry = self.pokedex_entry;
+        if status
+            .get_immune_types()
+            .iter()
+            .any(|element_type| entry.has_type(element_type))
+        {
+            return false;
+        }
+        let is_prevented_by_terrain = match field.get_terrain_for(self) {
+            Some(Terrain::Misty) => true,
+            Some(Terrain::Electric) => matches!(status, Status::Sleep(_)),
+            _ => false,
+        };
+        if is_prevented_by_terrain || self.get_ability_effects().prevents_status(&status, field) {
+            return false;
+        }
+        self.status = Some(status);
+        true
+    }
+
+    pub fn cure_status(&mut self) {
+        self.status = None;
+    }
+
+    /// Fully heals the pokemon and puts it to sleep, replacing any other status
+    /// Fails if the pokemon is already asleep or at full HP
+    pub fn rest(&mut self) -> bool {
+        if matches!(self.status, Some(Status::Sleep(_))) || self.current_hp == self.stats.hp {
+            return false;
+        }
+        self.current_hp = self.stats.hp;
+        self.status = Some(Status::Sleep(REST_SLEEP_TURNS));
+        true
+    }
+
+    /// Runs before the pokemon uses a move
+    pub fn on_status_before_move<R: Rng>(&mut self, rng: &mut R) -> BeforeMoveOutcome {
+        match self.status {
+            // Sleeping for n turns means n failed attempts, the pokemon wakes up on the next one
+            Some(Status::Sleep(0)) => {
+                self.status = None;
+                BeforeMoveOutcome::WokeUp
+            }
+            Some(Status::Sleep(turns_left)) => {
+                self.status = Some(Status::Sleep(turns_left - 1));
+                BeforeMoveOutcome::FastAsleep
+            }
+            Some(Status::Freeze) => {
+                if rng.gen_bool(THAW_CHANCE) {
+                    self.status = None;
+                    BeforeMoveOutcome::Thawed
+                } else {
+                    BeforeMoveOutcome::Frozen
+                }
+            }
+            Some(Status::Paralysis) if rng.gen_bool(FULL_PARALYSIS_CHANCE) => {
+                BeforeMoveOutcome::FullyParalyzed
+            }
+            _ => BeforeMoveOutcome::CanMove,
+        }
+    }
+
+    /// Runs at the end of every turn of a battle
+    /// Returns the damage taken
+    pub fn on_status_end_of_turn(&mut self) -> u16 {
+        let max_hp = self.stats.hp;
+        let damage = match self.status {
+            Some(Status::Burn) => max_hp / 16,
+            Some(Status::Poison) => max_hp / 8,
+            Some(Status::BadlyPoisoned(counter)) => {
+                let counter = counter.saturating_add(1).min(15);
+                self.status = Some(Status::BadlyPoisoned(counter));
+                max_hp * counter as u16 / 16
+            }
+            _ => return 0,
+        };
+        self.take_damage(damage.max(1))
+    }
+
+    /// Removes HP without going under 0
+    /// Returns the damage actually taken
+    pub fn take_damage(&mut self, damage: u16) -> u16 {
+        let damage = damage.min(self.current_hp);
+        self.current_hp -= damage;
+        damage
+    }
+
+    /// Restores HP without going over the maximum
+    /// Returns the HP actually restored
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        let amount = amount.min(self.stats.hp - self.current_hp);
+        self.current_hp += amount;
+        amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{
+        abilities::names::Name,
+        field::{Field, Terrain, Weather},
+        test_utils::{self, make_pokemon},
+        types::Type,
+    };
+
+    use super::{BeforeMoveOutcome, Status, REST_SLEEP_TURNS};
+
+    #[test]
+    fn sleep_lasts_for_the_whole_counter() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pokemon = make_pokemon(1, 50);
+        pokemon.status = Some(Status::Sleep(2));
+        let outcomes: Vec<BeforeMoveOutcome> = (0..3)
+            .map(|_| pokemon.on_status_before_move(&mut rng))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                BeforeMoveOutcome::FastAsleep,
+                BeforeMoveOutcome::FastAsleep,
+                BeforeMoveOutcome::WokeUp
+            ]
+        );
+        assert_eq!(pokemon.status, None);
+        assert_eq!(
+            pokemon.on_status_before_move(&mut rng),
+            BeforeMoveOutcome::CanMove
+        );
+    }
+
+    #[test]
+    fn random_sleep_lasts_one_to_three_turns() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let mut pokemon = make_pokemon(1, 50);
+            pokemon.status = Some(Status::random_sleep(&mut rng));
+            let mut nb_turns_asleep = 0;
+            while pokemon.on_status_before_move(&mut rng) == BeforeMoveOutcome::FastAsleep {
+                nb_turns_asleep += 1;
+            }
+            assert!((1..=3).contains(&nb_turns_asleep));
+        }
+    }
+
+    #[test]
+    fn toxic_damage_grows_until_the_cap() {
+        let mut pokemon = make_pokemon(1, 50);
+        let max_hp = pokemon.stats.hp;
+        pokemon.status = Some(Status::BadlyPoisoned(0));
+        for turn in 1..=20_u16 {
+            pokemon.current_hp = max_hp;
+            let damage = pokemon.on_status_end_of_turn();
+            let counter = turn.min(15);
+            assert_eq!(damage, max_hp * counter / 16);
+            assert_eq!(pokemon.status, Some(Status::BadlyPoisoned(counter as u8)));
+        }
+    }
+
+    #[test]
+    fn burn_and_poison_damage() {
+        let mut pokemon = make_pokemon(1, 50);
+        let max_hp = pokemon.stats.hp;
+        pokemon.status = Some(Status::Burn);
+        assert_eq!(pokemon.on_status_end_of_turn(), max_hp / 16);
+        pokemon.status = Some(Status::Poison);
+        assert_eq!(pokemon.on_status_end_of_turn(), max_hp / 8);
+    }
+
+    #[test]
+    fn type_immunities_prevent_the_status() {
+        let mut pokemon = make_pokemon(1, 50);
+        let field = Field::default();
+        assert!(!pokemon.try_set_status(Status::Poison, &field));
+        assert!(!pokemon.try_set_status(Status::BadlyPoisoned(0), &field));
+        assert!(pokemon.try_set_status(Status::Burn, &field));
+        // A pokemon only has one status at a time
+        assert!(!pokemon.try_set_status(Status::Paralysis, &field));
+        assert_eq!(pokemon.status, Some(Status::Burn));
+    }
+
+    #[test]
+    fn ability_immunities_prevent_the_status() {
+        let mut pokemon = make_pokemon(1, 50);
+        pokemon.ability = Name::LeafGuard;
+        let mut field = Field::default();
+        field.set_weather(Weather::Sun);
+        assert!(!pokemon.try_set_status(Status::Burn, &field));
+        assert!(pokemon.try_set_status(Status::Burn, &Field::default()));
+    }
+
+    #[test]
+    fn terrains_prevent_the_status_of_grounded_pokemons() {
+        let mut pokemon = make_pokemon(1, 50);
+        let mut field = Field::default();
+        field.set_terrain(Terrain::Electric);
+        assert!(!pokemon.try_set_status(Status::Sleep(2), &field));
+        assert!(pokemon.try_set_status(Status::Paralysis, &field));
+
+        let mut pokemon = make_pokemon(1, 50);
+        field.set_terrain(Terrain::Misty);
+        assert!(!pokemon.try_set_status(Status::Burn, &field));
+
+        // Flying pokemons aren't grounded
+        pokemon.pokedex_entry = test_utils::make_pokedex_entry(&[Type::Flying]);
+        assert!(pokemon.try_set_status(Status::Burn, &field));
+    }
+
+    #[test]
+    fn rest_fails_at_full_hp() {
+        let mut pokemon = make_pokemon(1, 50);
+        assert!(!pokemon.rest());
+        assert_eq!(pokemon.status, None);
+
+        pokemon.take_damage(10);
+        pokemon.status = Some(Status::Burn);
+        assert!(pokemon.rest());
+        assert_eq!(pokemon.current_hp, pokemon.stats.hp);
+        assert_eq!(pokemon.status, Some(Status::Sleep(REST_SLEEP_TURNS)));
+    }
+}
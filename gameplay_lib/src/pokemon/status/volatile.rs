@@ -0,0 +1,176 @@
+use rand::Rng;
+
+use crate::pokemon::Pokemon;
+
+use super::BeforeMoveOutcome;
+
+/// Chance for a confused pokemon to hurt itself instead of moving
+pub const CONFUSION_SELF_HIT_CHANCE: f64 = 1. / 3.;
+
+/// Status only lasting while the pokemon stays in battle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VolatileStatus {
+    /// Number of turns left before snapping out of confusion, 0 when not confused
+    pub confusion_turns: u8,
+    /// Drained every turn by Leech Seed
+    pub is_seeded: bool,
+    /// HP left to the substitute, 0 when there is none
+    pub substitute_hp: u16,
+    /// Number of successful Protect in a row, each one makes the next one less likely
+    pub nb_consecutive_protects: u8,
+    /// Protected for the current turn
+    pub is_protected: bool,
+    /// Only lasts for the current turn
+    pub is_flinching: bool,
+    /// Slot of the move being charged, it is used automatically on the next turn
+    pub charging_slot: Option<usize>,
+    /// Can't be hit while charging a move like Fly or Dig
+    pub is_semi_invulnerable: bool,
+}
+
+impl VolatileStatus {
+    pub fn is_confused(&self) -> bool {
+        self.confusion_turns > 0
+    }
+
+    /// Confusion lasting between two and five turns, fails if already confused
+    pub fn try_confuse<R: Rng>(&mut self, rng: &mut R) -> bool {
+        if self.is_confused() {
+            return false;
+        }
+        self.confusion_turns = rng.gen_range(2..=5);
+        true
+    }
+
+    pub fn has_substitute(&self) -> bool {
+        self.substitute_hp > 0
+    }
+
+    /// The substitute costs a quarter of the maximum HP of the pokemon
+    pub fn try_make_substitute(&mut self, pokemon: &mut Pokemon) -> bool {
+        let cost = pokemon.stats.hp / 4;
+        if self.has_substitute() || cost == 0 || pokemon.current_hp <= cost {
+            return false;
+        }
+        pokemon.take_damage(cost);
+        self.substitute_hp = cost;
+        true
+    }
+
+    /// The substitute takes the damage instead of the pokemon
+    /// Returns the damage absorbed
+    pub fn hit_substitute(&mut self, damage: u16) -> u16 {
+        let absorbed = damage.min(self.substitute_hp);
+        self.substitute_hp -= absorbed;
+        absorbed
+    }
+
+    /// Each consecutive Protect has a third of the chances of the previous one to work
+    pub fn try_protect<R: Rng>(&mut self, rng: &mut R) -> bool {
+        let chance = 1. / 3_f64.powi(self.nb_consecutive_protects as i32);
+        if rng.gen_bool(chance) {
+            self.nb_consecutive_protects = self.nb_consecutive_protects.saturating_add(1);
+            self.is_protected = true;
+            true
+        } else {
+            self.nb_consecutive_protects = 0;
+            false
+        }
+    }
+
+    /// Any move other than a protecting one resets the Protect chances
+    pub fn on_move_used(&mut self, is_protecting: bool) {
+        if !is_protecting {
+            self.nb_consecutive_protects = 0;
+        }
+    }
+
+    /// Runs before the pokemon uses a move, after its persistent status
+    pub fn on_before_move<R: Rng>(&mut self, rng: &mut R) -> BeforeMoveOutcome {
+        if self.is_flinching {
+            return BeforeMoveOutcome::Flinched;
+        }
+        if !self.is_confused() {
+            return BeforeMoveOutcome::CanMove;
+        }
+        self.confusion_turns -= 1;
+        if self.confusion_turns == 0 {
+            BeforeMoveOutcome::SnappedOutOfConfusion
+        } else if rng.gen_bool(CONFUSION_SELF_HIT_CHANCE) {
+            BeforeMoveOutcome::HurtItselfInConfusion
+        } else {
+            BeforeMoveOutcome::CanMove
+        }
+    }
+
+    /// Runs at the end of every turn of a battle
+    /// Returns the HP drained by Leech Seed, to be given to the opponent
+    pub fn on_end_of_turn(&mut self, pokemon: &mut Pokemon) -> u16 {
+        self.is_flinching = false;
+        self.is_protected = false;
+        if self.is_seeded && !pokemon.is_fainted() {
+            pokemon.take_damage((pokemon.stats.hp / 8).max(1))
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::pokemon::{status::BeforeMoveOutcome, test_utils::make_pokemon};
+
+    use super::VolatileStatus;
+
+    #[test]
+    fn confusion_wears_off() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut volatile = VolatileStatus::default();
+        assert!(volatile.try_confuse(&mut rng));
+        assert!(!volatile.try_confuse(&mut rng));
+        let nb_turns = volatile.confusion_turns;
+        assert!((2..=5).contains(&nb_turns));
+
+        for _ in 1..nb_turns {
+            assert_ne!(
+                volatile.on_before_move(&mut rng),
+                BeforeMoveOutcome::SnappedOutOfConfusion
+            );
+        }
+        assert_eq!(
+            volatile.on_before_move(&mut rng),
+            BeforeMoveOutcome::SnappedOutOfConfusion
+        );
+        assert!(!volatile.is_confused());
+    }
+
+    #[test]
+    fn substitute_costs_a_quarter_of_the_max_hp() {
+        let mut pokemon = make_pokemon(1, 50);
+        let cost = pokemon.stats.hp / 4;
+        let mut volatile = VolatileStatus::default();
+        assert!(volatile.try_make_substitute(&mut pokemon));
+        assert_eq!(pokemon.current_hp, pokemon.stats.hp - cost);
+        assert!(!volatile.try_make_substitute(&mut pokemon));
+
+        assert_eq!(volatile.hit_substitute(cost + 10), cost);
+        assert!(!volatile.has_substitute());
+
+        pokemon.current_hp = cost;
+        assert!(!volatile.try_make_substitute(&mut pokemon));
+    }
+
+    #[test]
+    fn protect_chances_reset_after_another_move() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut volatile = VolatileStatus::default();
+        assert!(volatile.try_protect(&mut rng));
+        assert_eq!(volatile.nb_consecutive_protects, 1);
+
+        volatile.on_move_used(false);
+        assert_eq!(volatile.nb_consecutive_protects, 0);
+        assert!(volatile.try_protect(&mut rng));
+    }
+}
@@ -0,0 +1,78 @@
+use core_lib::utils::debug::ErrorCode;
+
+use super::{egg::Egg, Pokemon};
+
+pub mod party;
+pub mod pc;
+mod save;
+
+/// Anything that takes a slot in the party or in a box
+pub enum StoredPokemon {
+    Pokemon(Pokemon),
+    Egg(Egg),
+}
+
+impl StoredPokemon {
+    /// `None` for an egg
+    pub fn get_pokemon(&self) -> Option<&Pokemon> {
+        match self {
+            StoredPokemon::Pokemon(pokemon) => Some(pokemon),
+            StoredPokemon::Egg(_) => None,
+        }
+    }
+
+    pub fn get_pokemon_mut(&mut self) -> Option<&mut Pokemon> {
+        match self {
+            StoredPokemon::Pokemon(pokemon) => Some(pokemon),
+            StoredPokemon::Egg(_) => None,
+        }
+    }
+
+    pub fn is_egg(&self) -> bool {
+        matches!(self, StoredPokemon::Egg(_))
+    }
+
+    /// A hatched pokemon which isn't fainted
+    pub fn is_able(&self) -> bool {
+        self.get_pokemon()
+            .is_some_and(|pokemon| !pokemon.is_fainted())
+    }
+
+    pub fn get_display_name(&self) -> String {
+        match self {
+            StoredPokemon::Pokemon(pokemon) => pokemon.get_display_name(),
+            StoredPokemon::Egg(_) => "Egg".to_string(),
+        }
+    }
+
+    /// Reads a pokemon or an egg from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        match toml.get("egg").and_then(|egg| egg.as_table()) {
+            Some(egg) => Ok(StoredPokemon::Egg(Egg::from_toml(egg)?)),
+            None => Ok(StoredPokemon::Pokemon(Pokemon::from_toml(toml)?)),
+        }
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        match self {
+            StoredPokemon::Pokemon(pokemon) => pokemon.to_toml(),
+            StoredPokemon::Egg(egg) => {
+                let mut toml = toml::Table::new();
+                toml.insert("egg".to_string(), egg.to_toml().into());
+                toml
+            }
+        }
+    }
+}
+
+impl From<Pokemon> for StoredPokemon {
+    fn from(pokemon: Pokemon) -> Self {
+        StoredPokemon::Pokemon(pokemon)
+    }
+}
+
+impl From<Egg> for StoredPokemon {
+    fn from(egg: Egg) -> Self {
+        StoredPokemon::Egg(egg)
+    }
+}
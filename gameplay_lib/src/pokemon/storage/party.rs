@@ -0,0 +1,167 @@
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+
+use crate::pokemon::Pokemon;
+
+use super::StoredPokemon;
+
+/// Maximum number of pokemons and eggs the player can carry
+pub const MAX_PARTY_SIZE: usize = 6;
+
+/// The pokemons and the eggs carried by the player, in order
+#[derive(Default)]
+pub struct Party {
+    members: Vec<StoredPokemon>,
+}
+
+impl Party {
+    pub fn get(&self, index: usize) -> Option<&StoredPokemon> {
+        self.members.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut StoredPokemon> {
+        self.members.get_mut(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StoredPokemon> {
+        self.members.iter()
+    }
+
+    /// The hatched pokemons, without the eggs
+    pub fn get_pokemons(&self) -> impl Iterator<Item = &Pokemon> {
+        self.members.iter().filter_map(StoredPokemon::get_pokemon)
+    }
+
+    pub fn get_pokemons_mut(&mut self) -> impl Iterator<Item = &mut Pokemon> {
+        self.members
+            .iter_mut()
+            .filter_map(StoredPokemon::get_pokemon_mut)
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_SIZE
+    }
+
+    pub fn has_able_pokemon(&self) -> bool {
+        self.members.iter().any(StoredPokemon::is_able)
+    }
+
+    /// Index of the first pokemon able to battle, eggs and fainted pokemons are skipped
+    pub fn get_lead(&self) -> Option<usize> {
+        self.members.iter().position(StoredPokemon::is_able)
+    }
+
+    pub fn get_lead_pokemon(&self) -> Option<&Pokemon> {
+        self.get_lead()
+            .and_then(|index| self.members[index].get_pokemon())
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), ErrorCode> {
+        if index >= self.members.len() {
+            error!("There is no pokemon at the index {} of the party", index);
+            return Err(ErrorCode::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Puts the pokemon or the egg at the end of the party
+    /// Returns its index
+    pub fn add(&mut self, member: StoredPokemon) -> Result<usize, ErrorCode> {
+        if self.is_full() {
+            error!(
+                "Can't add {} to the party, it already has {} members",
+                member.get_display_name(),
+                MAX_PARTY_SIZE
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        self.members.push(member);
+        Ok(self.members.len() - 1)
+    }
+
+    /// Fails when it would leave the party without any pokemon able to battle
+    /// The following members go up by one index
+    pub fn remove(&mut self, index: usize) -> Result<StoredPokemon, ErrorCode> {
+        self.check_index(index)?;
+        let is_last_able = self.members[index].is_able()
+            && self
+                .members
+                .iter()
+                .filter(|member| member.is_able())
+                .count()
+                == 1;
+        if is_last_able {
+            error!(
+                "Can't remove {} from the party, it is the last pokemon able to battle",
+                self.members[index].get_display_name()
+            );
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(self.members.remove(index))
+    }
+
+    pub fn swap(&mut self, index_1: usize, index_2: usize) -> Result<(), ErrorCode> {
+        self.check_index(index_1)?;
+        self.check_index(index_2)?;
+        self.members.swap(index_1, index_2);
+        Ok(())
+    }
+
+    /// Moves a member to the given index, the ones in between are shifted
+    pub fn move_to(&mut self, from: usize, to: usize) -> Result<(), ErrorCode> {
+        self.check_index(from)?;
+        self.check_index(to)?;
+        let member = self.members.remove(from);
+        self.members.insert(to, member);
+        Ok(())
+    }
+
+    /// Runs every time the player walks a step, the eggs hatch in place
+    /// Returns the indexes of the eggs which hatched
+    pub fn on_step(&mut self) -> Vec<usize> {
+        let mut hatched = Vec::new();
+        for index in 0..self.members.len() {
+            let is_hatching = match &mut self.members[index] {
+                StoredPokemon::Egg(egg) => egg.on_step(),
+                StoredPokemon::Pokemon(_) => false,
+            };
+            if !is_hatching {
+                continue;
+            }
+            if let StoredPokemon::Egg(egg) = self.members.remove(index) {
+                self.members
+                    .insert(index, StoredPokemon::Pokemon(egg.hatch()));
+                hatched.push(index);
+            }
+        }
+        hatched
+    }
+
+    /// Reads the party from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let mut party = Self::default();
+        for member in Toml::get_table_list(toml, "members")? {
+            party.add(StoredPokemon::from_toml(member)?)?;
+        }
+        Ok(party)
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let members: Vec<toml::Value> = self
+            .members
+            .iter()
+            .map(|member| member.to_toml().into())
+            .collect();
+        let mut toml = toml::Table::new();
+        toml.insert("members".to_string(), members.into());
+        toml
+    }
+}
@@ -0,0 +1,387 @@
+use std::str::FromStr;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+use log::error;
+
+use crate::pokemon::Pokemon;
+
+use super::{party::Party, StoredPokemon};
+
+pub const NB_SLOTS_PER_BOX: usize = 30;
+pub const NB_BOXES: usize = 8;
+
+/// Background of a box, only used by the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wallpaper {
+    Forest,
+    City,
+    Desert,
+    Savanna,
+    Crag,
+    Volcano,
+    Snow,
+    Cave,
+    Beach,
+    Seafloor,
+    River,
+    Sky,
+}
+
+impl Wallpaper {
+    pub const ALL: [Wallpaper; 12] = [
+        Wallpaper::Forest,
+        Wallpaper::City,
+        Wallpaper::Desert,
+        Wallpaper::Savanna,
+        Wallpaper::Crag,
+        Wallpaper::Volcano,
+        Wallpaper::Snow,
+        Wallpaper::Cave,
+        Wallpaper::Beach,
+        Wallpaper::Seafloor,
+        Wallpaper::River,
+        Wallpaper::Sky,
+    ];
+}
+
+impl FromStr for Wallpaper {
+    type Err = ErrorCode;
+
+    fn from_str(wallpaper: &str) -> Result<Self, ErrorCode> {
+        match wallpaper {
+            "Forest" => Ok(Wallpaper::Forest),
+            "City" => Ok(Wallpaper::City),
+            "Desert" => Ok(Wallpaper::Desert),
+            "Savanna" => Ok(Wallpaper::Savanna),
+            "Crag" => Ok(Wallpaper::Crag),
+            "Volcano" => Ok(Wallpaper::Volcano),
+            "Snow" => Ok(Wallpaper::Snow),
+            "Cave" => Ok(Wallpaper::Cave),
+            "Beach" => Ok(Wallpaper::Beach),
+            "Seafloor" => Ok(Wallpaper::Seafloor),
+            "River" => Ok(Wallpaper::River),
+            "Sky" => Ok(Wallpaper::Sky),
+            _ => {
+                error!("The wallpaper {} is not a valid box wallpaper", wallpaper);
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+}
+
+/// A slot of a box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxPosition {
+    pub box_index: usize,
+    pub slot: usize,
+}
+
+/// A named box of `NB_SLOTS_PER_BOX` slots, which can be left empty
+pub struct PcBox {
+    pub name: String,
+    pub wallpaper: Wallpaper,
+    slots: Vec<Option<StoredPokemon>>,
+}
+
+impl PcBox {
+    pub fn new(name: String, wallpaper: Wallpaper) -> Self {
+        Self {
+            name,
+            wallpaper,
+            slots: (0..NB_SLOTS_PER_BOX).map(|_| None).collect(),
+        }
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&StoredPokemon> {
+        self.slots.get(slot).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut StoredPokemon> {
+        self.slots.get_mut(slot).and_then(Option::as_mut)
+    }
+
+    pub fn get_first_free_slot(&self) -> Option<usize> {
+        self.slots.iter().position(Option::is_none)
+    }
+
+    pub fn get_nb_pokemons(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.get_first_free_slot().is_none()
+    }
+
+    /// Reads a box from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let name = Toml::get_string(toml, "name")?;
+        let wallpaper = Wallpaper::from_str(&Toml::get_string(toml, "wallpaper")?)?;
+        let mut pc_box = Self::new(name, wallpaper);
+        for member in Toml::get_table_list(toml, "pokemons")? {
+            let slot = Toml::get_u8(member, "slot")? as usize;
+            if slot >= NB_SLOTS_PER_BOX || pc_box.slots[slot].is_some() {
+                error!(
+                    "The slot {} of the box {} is not a free slot",
+                    slot, pc_box.name
+                );
+                return Err(ErrorCode::BadValue);
+            }
+            pc_box.slots[slot] = Some(StoredPokemon::from_toml(member)?);
+        }
+        Ok(pc_box)
+    }
+
+    /// Only the occupied slots are written, along with their index
+    pub fn to_toml(&self) -> toml::Table {
+        let mut pokemons: Vec<toml::Value> = Vec::new();
+        for (slot, member) in self.slots.iter().enumerate() {
+            if let Some(member) = member {
+                let mut member_toml = member.to_toml();
+                member_toml.insert("slot".to_string(), (slot as i64).into());
+                pokemons.push(member_toml.into());
+            }
+        }
+        let mut toml = toml::Table::new();
+        toml.insert("name".to_string(), self.name.clone().into());
+        toml.insert(
+            "wallpaper".to_string(),
+            format!("{:?}", self.wallpaper).into(),
+        );
+        toml.insert("pokemons".to_string(), pokemons.into());
+        toml
+    }
+}
+
+/// Every box of the PC, the pokemons which don't fit in the party go here
+pub struct Pc {
+    pub boxes: Vec<PcBox>,
+}
+
+impl Default for Pc {
+    fn default() -> Self {
+        let boxes = (0..NB_BOXES)
+            .map(|index| {
+                let wallpaper = Wallpaper::ALL[index % Wallpaper::ALL.len()];
+                PcBox::new(format!("Box {}", index + 1), wallpaper)
+            })
+            .collect();
+        Self { boxes }
+    }
+}
+
+impl Pc {
+    pub fn get_box(&self, box_index: usize) -> Result<&PcBox, ErrorCode> {
+        match self.boxes.get(box_index) {
+            Some(pc_box) => Ok(pc_box),
+            None => {
+                error!("There is no box at the index {}", box_index);
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+
+    pub fn get_box_mut(&mut self, box_index: usize) -> Result<&mut PcBox, ErrorCode> {
+        match self.boxes.get_mut(box_index) {
+            Some(pc_box) => Ok(pc_box),
+            None => {
+                error!("There is no box at the index {}", box_index);
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+
+    pub fn get(&self, position: BoxPosition) -> Option<&StoredPokemon> {
+        self.boxes
+            .get(position.box_index)
+            .and_then(|pc_box| pc_box.get(position.slot))
+    }
+
+    fn get_slot_mut(
+        &mut self,
+        position: BoxPosition,
+    ) -> Result<&mut Option<StoredPokemon>, ErrorCode> {
+        let pc_box = self.get_box_mut(position.box_index)?;
+        match pc_box.slots.get_mut(position.slot) {
+            Some(slot) => Ok(slot),
+            None => {
+                error!(
+                    "There is no slot {} in the box {}",
+                    position.slot, pc_box.name
+                );
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+
+    /// Takes the pokemon or the egg out of its slot
+    fn take(&mut self, position: BoxPosition) -> Result<StoredPokemon, ErrorCode> {
+        match self.get_slot_mut(position)?.take() {
+            Some(member) => Ok(member),
+            None => {
+                error!("The slot {:?} is empty", position);
+                Err(ErrorCode::NotFound)
+            }
+        }
+    }
+
+    /// Puts the pokemon or the egg in the first free slot, starting from the given box
+    /// Used when the party is full, for example after a capture
+    pub fn store(
+        &mut self,
+        member: StoredPokemon,
+        first_box_index: usize,
+    ) -> Result<BoxPosition, ErrorCode> {
+        let nb_boxes = self.boxes.len();
+        let position = (0..nb_boxes)
+            .map(|offset| (first_box_index + offset) % nb_boxes)
+            .find_map(|box_index| {
+                self.boxes[box_index]
+                    .get_first_free_slot()
+                    .map(|slot| BoxPosition { box_index, slot })
+            });
+        match position {
+            Some(position) => {
+                *self.get_slot_mut(position)? = Some(member);
+                Ok(position)
+            }
+            None => {
+                error!(
+                    "Can't store {}, every box is full",
+                    member.get_display_name()
+                );
+                Err(ErrorCode::BadValue)
+            }
+        }
+    }
+
+    /// Moves a member of the party to the first free slot of the given box
+    pub fn deposit(
+        &mut self,
+        party: &mut Party,
+        party_index: usize,
+        box_index: usize,
+    ) -> Result<BoxPosition, ErrorCode> {
+        let pc_box = self.get_box_mut(box_index)?;
+        let slot = match pc_box.get_first_free_slot() {
+            Some(slot) => slot,
+            None => {
+                error!("Can't deposit in the box {}, it is full", pc_box.name);
+                return Err(ErrorCode::BadValue);
+            }
+        };
+        pc_box.slots[slot] = Some(party.remove(party_index)?);
+        Ok(BoxPosition { box_index, slot })
+    }
+
+    /// Moves a pokemon or an egg at the end of the party
+    /// Returns its index in the party
+    pub fn withdraw(
+        &mut self,
+        party: &mut Party,
+        position: BoxPosition,
+    ) -> Result<usize, ErrorCode> {
+        if party.is_full() {
+            error!("Can't withdraw the slot {:?}, the party is full", position);
+            return Err(ErrorCode::BadValue);
+        }
+        let member = self.take(position)?;
+        party.add(member)
+    }
+
+    /// Moves a pokemon or an egg to another slot, swapping it with what was there
+    pub fn move_to(&mut self, from: BoxPosition, to: BoxPosition) -> Result<(), ErrorCode> {
+        self.get_slot_mut(to)?;
+        if from == to {
+            return Ok(());
+        }
+        let member = self.take(from)?;
+        let previous = self.get_slot_mut(to)?.replace(member);
+        *self.get_slot_mut(from)? = previous;
+        Ok(())
+    }
+
+    /// Removes a pokemon for good, eggs can't be released
+    pub fn release(&mut self, position: BoxPosition) -> Result<Pokemon, ErrorCode> {
+        if self.get(position).is_some_and(StoredPokemon::is_egg) {
+            error!("Can't release the egg in the slot {:?}", position);
+            return Err(ErrorCode::BadValue);
+        }
+        match self.take(position)? {
+            StoredPokemon::Pokemon(pokemon) => Ok(pokemon),
+            StoredPokemon::Egg(_) => Err(ErrorCode::BadValue),
+        }
+    }
+
+    /// Reads every box from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let boxes = Toml::get_table_list(toml, "boxes")?
+            .into_iter()
+            .map(PcBox::from_toml)
+            .collect::<Result<Vec<PcBox>, ErrorCode>>()?;
+        if boxes.is_empty() {
+            error!("The PC must have at least one box");
+            return Err(ErrorCode::BadValue);
+        }
+        Ok(Self { boxes })
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let boxes: Vec<toml::Value> = self
+            .boxes
+            .iter()
+            .map(|pc_box| pc_box.to_toml().into())
+            .collect();
+        let mut toml = toml::Table::new();
+        toml.insert("boxes".to_string(), boxes.into());
+        toml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pokemon::test_utils::make_pokemon;
+
+    use super::{BoxPosition, Pc};
+
+    fn get_pokedex_number(pc: &Pc, position: BoxPosition) -> Option<u16> {
+        pc.get(position)?
+            .get_pokemon()
+            .map(|pokemon| pokemon.pokedex_entry.pokedex_number)
+    }
+
+    #[test]
+    fn moving_to_the_same_slot_keeps_the_pokemon() {
+        let mut pc = Pc::default();
+        let position = pc.store(make_pokemon(1, 5).into(), 0).unwrap();
+        pc.move_to(position, position).unwrap();
+        assert_eq!(get_pokedex_number(&pc, position), Some(1));
+    }
+
+    #[test]
+    fn moving_swaps_with_the_other_slot() {
+        let mut pc = Pc::default();
+        let first = pc.store(make_pokemon(1, 5).into(), 0).unwrap();
+        let second = pc.store(make_pokemon(2, 20).into(), 0).unwrap();
+        pc.move_to(first, second).unwrap();
+        assert_eq!(get_pokedex_number(&pc, first), Some(2));
+        assert_eq!(get_pokedex_number(&pc, second), Some(1));
+
+        let empty = BoxPosition {
+            box_index: 1,
+            slot: 4,
+        };
+        pc.move_to(first, empty).unwrap();
+        assert!(pc.get(first).is_none());
+        assert_eq!(get_pokedex_number(&pc, empty), Some(2));
+
+        // Nothing moves when a slot is missing or empty
+        let missing = BoxPosition {
+            box_index: pc.boxes.len(),
+            slot: 0,
+        };
+        assert!(pc.move_to(empty, missing).is_err());
+        assert!(pc.move_to(first, second).is_err());
+        assert_eq!(get_pokedex_number(&pc, empty), Some(2));
+        assert_eq!(get_pokedex_number(&pc, second), Some(1));
+    }
+}
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use core_lib::utils::{debug::ErrorCode, toml::Toml};
+
+use crate::pokemon::{
+    abilities,
+    daycare::Daycare,
+    egg::Egg,
+    moves::moveset::Moveset,
+    natures::{self, get_global_natures},
+    personality::{ShinyOdds, TrainerId},
+    pokedex::{breeding::Gender, get_global_pokedex_entry},
+    stats::Stats,
+    status::Status,
+    Pokemon,
+};
+
+impl Status {
+    /// The number of turns counted by the status, if any
+    fn get_counter(&self) -> Option<u8> {
+        match self {
+            Status::BadlyPoisoned(counter) | Status::Sleep(counter) => Some(*counter),
+            _ => None,
+        }
+    }
+
+    fn with_counter(self, counter: u8) -> Self {
+        match self {
+            Status::BadlyPoisoned(_) => Status::BadlyPoisoned(counter),
+            Status::Sleep(_) => Status::Sleep(counter),
+            status => status,
+        }
+    }
+
+    fn get_name(&self) -> &'static str {
+        match self {
+            Status::Burn => "Burn",
+            Status::Freeze => "Freeze",
+            Status::Paralysis => "Paralysis",
+            Status::Poison => "Poison",
+            Status::BadlyPoisoned(_) => "BadlyPoisoned",
+            Status::Sleep(_) => "Sleep",
+        }
+    }
+}
+
+impl TrainerId {
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        Ok(Self {
+            id: Toml::get_u16(toml, "id")?,
+            secret_id: Toml::get_u16(toml, "secret_id")?,
+        })
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        toml.insert("id".to_string(), i64::from(self.id).into());
+        toml.insert("secret_id".to_string(), i64::from(self.secret_id).into());
+        toml
+    }
+}
+
+impl ShinyOdds {
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        Ok(Self {
+            nb_rolls: Toml::get_u8(toml, "nb_rolls")?,
+            has_shiny_charm: Toml::get_bool(toml, "has_shiny_charm")?,
+        })
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        toml.insert("nb_rolls".to_string(), self.nb_rolls.into());
+        toml.insert("has_shiny_charm".to_string(), self.has_shiny_charm.into());
+        toml
+    }
+}
+
+impl Pokemon {
+    fn get_status_from_toml(toml: &toml::Table) -> Result<Option<Status>, ErrorCode> {
+        if !toml.contains_key("status") {
+            return Ok(None);
+        }
+        let status = Status::from_str(&Toml::get_string(toml, "status")?)?;
+        let counter = if toml.contains_key("status_counter") {
+            Toml::get_u8(toml, "status_counter")?
+        } else {
+            0
+        };
+        Ok(Some(status.with_counter(counter)))
+    }
+
+    /// Reads a pokemon from a save file
+    /// The stats are computed again instead of being read
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let pokedex_entry = get_global_pokedex_entry(Toml::get_u16(toml, "species")?)?;
+        let nature_name = natures::names::Name::from_str(&Toml::get_string(toml, "nature")?)?;
+        let held_item = if toml.contains_key("held_item") {
+            Some(Toml::get_string(toml, "held_item")?)
+        } else {
+            None
+        };
+
+        let mut pokemon = Self {
+            pokedex_entry,
+            nickname: Toml::get_string(toml, "nickname")?,
+            id: Toml::get_usize(toml, "id")?,
+            gender: Gender::from_str(&Toml::get_string(toml, "gender")?)?,
+            ability: abilities::names::Name::from_str(&Toml::get_string(toml, "ability")?)?,
+            personality_value: Toml::get_u32(toml, "personality_value")?,
+            original_trainer: TrainerId::from_toml(Toml::get_table(toml, "original_trainer")?)?,
+            held_item,
+            friendship: Toml::get_u8(toml, "friendship")?,
+            level: Toml::get_u8(toml, "level")?,
+            experience: Toml::get_u32(toml, "experience")?,
+            nature: get_global_natures()?.get(nature_name)?,
+            moveset: Moveset::from_toml(&Toml::get_table_list(toml, "moves")?)?,
+            evs: Stats::from_toml(Toml::get_table(toml, "evs")?)?,
+            ivs: Stats::from_toml(Toml::get_table(toml, "ivs")?)?,
+            stats: Stats::default(),
+            current_hp: 0,
+            status: Self::get_status_from_toml(toml)?,
+        };
+        pokemon.update_stats();
+        pokemon.current_hp = Toml::get_u16(toml, "current_hp")?.min(pokemon.stats.hp);
+        Ok(pokemon)
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        let species = i64::from(self.pokedex_entry.pokedex_number);
+        toml.insert("species".to_string(), species.into());
+        toml.insert("nickname".to_string(), self.nickname.clone().into());
+        toml.insert("id".to_string(), (self.id as i64).into());
+        toml.insert("gender".to_string(), format!("{:?}", self.gender).into());
+        toml.insert("ability".to_string(), format!("{:?}", self.ability).into());
+        toml.insert(
+            "personality_value".to_string(),
+            i64::from(self.personality_value).into(),
+        );
+        toml.insert(
+            "original_trainer".to_string(),
+            self.original_trainer.to_toml().into(),
+        );
+        if let Some(held_item) = &self.held_item {
+            toml.insert("held_item".to_string(), held_item.clone().into());
+        }
+        toml.insert("friendship".to_string(), self.friendship.into());
+        toml.insert("level".to_string(), self.level.into());
+        toml.insert("experience".to_string(), i64::from(self.experience).into());
+        toml.insert(
+            "nature".to_string(),
+            format!("{:?}", self.nature.name).into(),
+        );
+        toml.insert("moves".to_string(), self.moveset.to_toml().into());
+        toml.insert("evs".to_string(), self.evs.to_toml().into());
+        toml.insert("ivs".to_string(), self.ivs.to_toml().into());
+        toml.insert("current_hp".to_string(), i64::from(self.current_hp).into());
+        if let Some(status) = self.status {
+            toml.insert("status".to_string(), status.get_name().into());
+            if let Some(counter) = status.get_counter() {
+                toml.insert("status_counter".to_string(), counter.into());
+            }
+        }
+        toml
+    }
+}
+
+impl Egg {
+    /// Reads an egg and the pokemon inside from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let mut egg = Self::new(Pokemon::from_toml(Toml::get_table(toml, "pokemon")?)?);
+        egg.nb_cycles_left = Toml::get_u8(toml, "nb_cycles_left")?;
+        egg.nb_steps = Toml::get_u8(toml, "nb_steps")?;
+        Ok(egg)
+    }
+
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        toml.insert("nb_cycles_left".to_string(), self.nb_cycles_left.into());
+        toml.insert("nb_steps".to_string(), self.nb_steps.into());
+        toml.insert("pokemon".to_string(), self.pokemon.to_toml().into());
+        toml
+    }
+}
+
+impl Daycare {
+    fn get_pokemon_from_toml(toml: &toml::Table, key: &str) -> Result<Option<Pokemon>, ErrorCode> {
+        if !toml.contains_key(key) {
+            return Ok(None);
+        }
+        Ok(Some(Pokemon::from_toml(Toml::get_table(toml, key)?)?))
+    }
+
+    /// Reads the deposited pokemons and the egg waiting to be collected from a save file
+    pub fn from_toml(toml: &toml::Table) -> Result<Self, ErrorCode> {
+        let egg = if toml.contains_key("egg") {
+            Some(Egg::from_toml(Toml::get_table(toml, "egg")?)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            pokemon_1: Self::get_pokemon_from_toml(toml, "pokemon_1")?,
+            pokemon_2: Self::get_pokemon_from_toml(toml, "pokemon_2")?,
+            egg,
+            nb_steps: Toml::get_u8(toml, "nb_steps")?,
+        })
+    }
+
+    /// The empty slots are left out
+    pub fn to_toml(&self) -> toml::Table {
+        let mut toml = toml::Table::new();
+        if let Some(pokemon) = &self.pokemon_1 {
+            toml.insert("pokemon_1".to_string(), pokemon.to_toml().into());
+        }
+        if let Some(pokemon) = &self.pokemon_2 {
+            toml.insert("pokemon_2".to_string(), pokemon.to_toml().into());
+        }
+        if let Some(egg) = &self.egg {
+            toml.insert("egg".to_string(), egg.to_toml().into());
+        }
+        toml.insert("nb_steps".to_string(), self.nb_steps.into());
+        toml
+    }
+}
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use core_lib::utils::debug::ErrorCode;
 use log::error;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Type {
     Normal,
     Fire,
@@ -59,3 +59,151 @@ impl FromStr for Type {
         }
     }
 }
+
+impl Type {
+    /// The 18 types that take part in the effectiveness chart
+    pub const BATTLE_TYPES: [Type; 18] = [
+        Type::Normal,
+        Type::Fire,
+        Type::Fighting,
+        Type::Water,
+        Type::Flying,
+        Type::Grass,
+        Type::Poison,
+        Type::Electric,
+        Type::Ground,
+        Type::Psychic,
+        Type::Rock,
+        Type::Ice,
+        Type::Bug,
+        Type::Dragon,
+        Type::Ghost,
+        Type::Dark,
+        Type::Steel,
+        Type::Fairy,
+    ];
+
+    /// Multiplier of an attack of this type against a single defending type
+    /// `Stellar` and `Unknown` are neutral both when attacking and when defending
+    pub fn get_effectiveness(&self, defender: &Type) -> f32 {
+        use Type::*;
+        match (self, defender) {
+            (Stellar | Unknown, _) | (_, Stellar | Unknown) => 1.,
+
+            (Normal, Rock | Steel) => 0.5,
+            (Normal, Ghost) => 0.,
+
+            (Fire, Grass | Ice | Bug | Steel) => 2.,
+            (Fire, Fire | Water | Rock | Dragon) => 0.5,
+
+            (Fighting, Normal | Ice | Rock | Dark | Steel) => 2.,
+            (Fighting, Flying | Poison | Psychic | Bug | Fairy) => 0.5,
+            (Fighting, Ghost) => 0.,
+
+            (Water, Fire | Ground | Rock) => 2.,
+            (Water, Water | Grass | Dragon) => 0.5,
+
+            (Flying, Fighting | Grass | Bug) => 2.,
+            (Flying, Electric | Rock | Steel) => 0.5,
+
+            (Grass, Water | Ground | Rock) => 2.,
+            (Grass, Fire | Flying | Grass | Poison | Bug | Dragon | Steel) => 0.5,
+
+            (Poison, Grass | Fairy) => 2.,
+            (Poison, Poison | Ground | Rock | Ghost) => 0.5,
+            (Poison, Steel) => 0.,
+
+            (Electric, Water | Flying) => 2.,
+            (Electric, Grass | Electric | Dragon) => 0.5,
+            (Electric, Ground) => 0.,
+
+            (Ground, Fire | Poison | Electric | Rock | Steel) => 2.,
+            (Ground, Grass | Bug) => 0.5,
+            (Ground, Flying) => 0.,
+
+            (Psychic, Fighting | Poison) => 2.,
+            (Psychic, Psychic | Steel) => 0.5,
+            (Psychic, Dark) => 0.,
+
+            (Rock, Fire | Flying | Ice | Bug) => 2.,
+            (Rock, Fighting | Ground | Steel) => 0.5,
+
+            (Ice, Grass | Flying | Ground | Dragon) => 2.,
+            (Ice, Fire | Water | Ice | Steel) => 0.5,
+
+            (Bug, Grass | Psychic | Dark) => 2.,
+            (Bug, Fire | Fighting | Flying | Poison | Ghost | Steel | Fairy) => 0.5,
+
+            (Dragon, Dragon) => 2.,
+            (Dragon, Steel) => 0.5,
+            (Dragon, Fairy) => 0.,
+
+            (Ghost, Psychic | Ghost) => 2.,
+            (Ghost, Dark) => 0.5,
+            (Ghost, Normal) => 0.,
+
+            (Dark, Psychic | Ghost) => 2.,
+            (Dark, Fighting | Dark | Fairy) => 0.5,
+
+            (Steel, Ice | Rock | Fairy) => 2.,
+            (Steel, Fire | Water | Electric | Steel) => 0.5,
+
+            (Fairy, Fighting | Dragon | Dark) => 2.,
+            (Fairy, Fire | Poison | Steel) => 0.5,
+
+            _ => 1.,
+        }
+    }
+
+    /// Multiplier of an attack of this type against a pair of defending types
+    /// Can be 0, 0.25, 0.5, 1, 2 or 4
+    pub fn get_dual_effectiveness(&self, defender_1: &Type, defender_2: Option<&Type>) -> f32 {
+        let multiplier = self.get_effectiveness(defender_1);
+        match defender_2 {
+            Some(defender_2) if defender_2 != defender_1 => {
+                multiplier * self.get_effectiveness(defender_2)
+            }
+            _ => multiplier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Type;
+
+    #[test]
+    fn single_type_matchups() {
+        assert_eq!(Type::Fire.get_effectiveness(&Type::Grass), 2.);
+        assert_eq!(Type::Water.get_effectiveness(&Type::Grass), 0.5);
+        assert_eq!(Type::Normal.get_effectiveness(&Type::Ghost), 0.);
+        assert_eq!(Type::Normal.get_effectiveness(&Type::Normal), 1.);
+        assert_eq!(Type::Stellar.get_effectiveness(&Type::Ghost), 1.);
+    }
+
+    #[test]
+    fn dual_type_matchups_multiply() {
+        assert_eq!(
+            Type::Fire.get_dual_effectiveness(&Type::Grass, Some(&Type::Bug)),
+            4.
+        );
+        assert_eq!(
+            Type::Grass.get_dual_effectiveness(&Type::Fire, Some(&Type::Dragon)),
+            0.25
+        );
+        assert_eq!(
+            Type::Ground.get_dual_effectiveness(&Type::Fire, Some(&Type::Flying)),
+            0.
+        );
+        assert_eq!(
+            Type::Fire.get_dual_effectiveness(&Type::Grass, Some(&Type::Water)),
+            1.
+        );
+        // A repeated type only counts once
+        assert_eq!(
+            Type::Fire.get_dual_effectiveness(&Type::Grass, Some(&Type::Grass)),
+            2.
+        );
+        assert_eq!(Type::Fire.get_dual_effectiveness(&Type::Grass, None), 2.);
+    }
+}